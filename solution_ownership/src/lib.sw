@@ -0,0 +1,81 @@
+library ownership;
+
+use std::{identity::Identity, option::Option, revert::require};
+
+pub enum OwnershipError {
+    NoOwner: (),
+    NoPendingOwner: (),
+    SenderNotOwner: (),
+    SenderNotPendingOwner: (),
+}
+
+// two-step ownership: the owner proposes a new owner, who then has to accept before anything changes
+pub struct Ownership {
+    owner: Option<Identity>,
+    pending_owner: Option<Identity>,
+}
+
+impl Ownership {
+    pub fn new(owner: Identity) -> Self {
+        Ownership {
+            owner: Option::Some(owner),
+            pending_owner: Option::None,
+        }
+    }
+
+    pub fn uninitialized() -> Self {
+        Ownership {
+            owner: Option::None,
+            pending_owner: Option::None,
+        }
+    }
+
+    // the current owner, reverting if ownership was never set or has been renounced
+    pub fn owner(self) -> Identity {
+        require(self.owner.is_some(), OwnershipError::NoOwner);
+        self.owner.unwrap()
+    }
+
+    // the proposed owner, reverting if there is no proposal
+    pub fn pending_owner(self) -> Identity {
+        require(self.pending_owner.is_some(), OwnershipError::NoPendingOwner);
+        self.pending_owner.unwrap()
+    }
+
+    // revert unless `sender` is the current owner
+    pub fn only_owner(self, sender: Identity) {
+        require(is(self.owner, sender), OwnershipError::SenderNotOwner);
+    }
+
+    // propose `new_owner`, replacing any earlier proposal
+    pub fn transfer(self, sender: Identity, new_owner: Identity) -> Self {
+        self.only_owner(sender);
+        Ownership {
+            owner: self.owner,
+            pending_owner: Option::Some(new_owner),
+        }
+    }
+
+    // make the proposed owner the owner
+    pub fn accept(self, sender: Identity) -> Self {
+        require(is(self.pending_owner, sender), OwnershipError::SenderNotPendingOwner);
+        Ownership {
+            owner: Option::Some(sender),
+            pending_owner: Option::None,
+        }
+    }
+
+    // give up ownership for good, along with any pending proposal
+    pub fn renounce(self, sender: Identity) -> Self {
+        self.only_owner(sender);
+        Ownership::uninitialized()
+    }
+}
+
+// true if `role` is held by `identity`
+fn is(role: Option<Identity>, identity: Identity) -> bool {
+    match role {
+        Option::Some(holder) => holder == identity,
+        Option::None => false,
+    }
+}
@@ -0,0 +1,34 @@
+library errors;
+
+pub enum AccessError {
+    SenderNotMinter: (),
+}
+
+pub enum AirdropError {
+    AirdropExhausted: (),
+    AlreadyClaimed: (),
+    InvalidProof: (),
+    ProofTooLong: (),
+}
+
+pub enum InitializationError {
+    CannotReinitialize: (),
+    ContractNotInitialized: (),
+    SenderNotAdmin: (),
+}
+
+pub enum SupplyError {
+    AllowanceExceeded: (),
+    MaxSupplyExceeded: (),
+}
+
+pub enum UserError {
+    AmountCannotBeZero: (),
+    IncorrectAssetSent: (),
+}
+
+pub enum VestingError {
+    InvalidSchedule: (),
+    NothingToRelease: (),
+    ScheduleAlreadyExists: (),
+}
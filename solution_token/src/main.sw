@@ -1,18 +1,406 @@
 contract;
 
+dep token_library;
+dep errors;
+
+use token_library::*;
+use errors::*;
+use ownership::*;
+
 use std::{
     address::Address,
-    context::call_frames::contract_id,
-    token::mint_to_address
+    block::height,
+    chain::auth::msg_sender,
+    context::{call_frames::{contract_id, msg_asset_id}, msg_amount},
+    hash::sha256,
+    identity::Identity,
+    logging::log,
+    storage::StorageMap,
+    option::*,
+    result::*,
+    revert::require,
+    token::{burn, mint_to_address, mint_to_contract},
 };
 
-abi MyToken {
-    fn mint_and_send_to_address(amount: u64, recipient: Address) -> bool;
+const MAX_BATCH_SIZE = 10;
+const MAX_PROOF_LENGTH = 16;
+// keeps `VestingSchedule::vested` from overflowing, see the comment there
+const MAX_VESTING_DURATION = 0xffffffff;
+
+storage {
+    // the only address that can initialize the contract, so that nobody can make themselves owner and minter
+    // before the deployer does. the deployer sets it by overriding the storage slot of this field,
+    // sha256("storage_0"), when deploying. nobody can initialize the contract if it is left unset
+    admin: b256 = 0x0000000000000000000000000000000000000000000000000000000000000000,
+
+    // tells us if it is initialized yet or not
+    state: State = State::NotInitialized,
+
+    // the identity that administers the minters and the airdrop
+    ownership: Ownership = Ownership {
+        owner: Option::None,
+        pending_owner: Option::None,
+    },
+
+    // the identities that are allowed to mint tokens
+    minters: StorageMap<Identity, bool> = StorageMap {},
+
+    // the number of tokens each minter can still mint
+    minter_allowances: StorageMap<Identity, u64> = StorageMap {},
+
+    // the number of tokens currently in circulation
+    total_supply: u64 = 0,
+
+    // the number of tokens that can ever be in circulation
+    max_supply: u64 = 0,
+
+    // the number of tokens set aside for airdrop claims and vesting schedules, which other mints cannot dip into
+    reserved_supply: u64 = 0,
+
+    // the human readable name of the token
+    name: str[32] = "                                ",
+
+    // the ticker symbol of the token
+    symbol: str[8] = "        ",
+
+    // the number of decimals used to display token amounts
+    decimals: u8 = 0,
+
+    // the merkle root of the (index, identity, amount) leaves that can be claimed
+    airdrop_root: b256 = 0x0000000000000000000000000000000000000000000000000000000000000000,
+
+    // the number of reserved tokens that can still be claimed from the current airdrop
+    airdrop_remaining: u64 = 0,
+
+    // incremented every time the airdrop root is set, so that each airdrop has its own claimed bitmap
+    airdrop_epoch: u64 = 0,
+
+    // bitmap of claimed leaf indices of each airdrop keyed by (epoch, word), each word holds 64 indices
+    claimed: StorageMap<(u64, u64), u64> = StorageMap {},
+
+    // the vesting schedule of each beneficiary, a duration of 0 means there is no schedule
+    vesting_schedules: StorageMap<Identity, VestingSchedule> = StorageMap {},
 }
 
 impl MyToken for Contract {
+    // initialize with the administrator, the token metadata and the supply cap, only the admin can initialize
+    #[storage(read, write)]
+    fn initialize(owner: Identity, name: str[32], symbol: str[8], decimals: u8, max_supply: u64) {
+        require(storage.state == State::NotInitialized, InitializationError::CannotReinitialize);
+        require(msg_sender().unwrap() == Identity::Address(Address { value: storage.admin }), InitializationError::SenderNotAdmin);
+
+        storage.ownership = Ownership::new(owner);
+        storage.name = name;
+        storage.symbol = symbol;
+        storage.decimals = decimals;
+        storage.max_supply = max_supply;
+        storage.state = State::Initialized;
+    }
+
+    // mint tokens and send them to an address, only minters can mint
+    #[storage(read, write)]
     fn mint_and_send_to_address(amount: u64, recipient: Address) -> bool {
-        mint_to_address(amount, recipient);
+        spend_minter_allowance(amount);
+        mint_to(amount, Identity::Address(recipient));
+        true
+    }
+
+    // mint tokens and send them to an address or a contract, only minters can mint
+    #[storage(read, write)]
+    fn mint_and_send_to_identity(amount: u64, recipient: Identity) -> bool {
+        spend_minter_allowance(amount);
+        mint_to(amount, recipient);
         true
     }
+
+    // mint tokens for up to MAX_BATCH_SIZE recipients in one call, only minters can mint
+    //
+    // returns the number of recipients that received tokens
+    #[storage(read, write)]
+    fn mint_batch(airdrops: [Airdrop; 10]) -> u64 {
+        let mut minted = 0;
+
+        let mut i = 0;
+        while i < MAX_BATCH_SIZE {
+            let airdrop = airdrops[i];
+            if 0 < airdrop.amount {
+                spend_minter_allowance(airdrop.amount);
+                mint_to(airdrop.amount, airdrop.recipient);
+                minted += 1;
+            }
+            i += 1;
+        }
+
+        minted
+    }
+
+    // propose a new owner, who has to call `accept_ownership` before the transfer takes effect
+    #[storage(read, write)]
+    fn transfer_ownership(new_owner: Identity) {
+        storage.ownership = storage.ownership.transfer(msg_sender().unwrap(), new_owner);
+    }
+
+    // become the owner, only the proposed owner can accept
+    #[storage(read, write)]
+    fn accept_ownership() {
+        storage.ownership = storage.ownership.accept(msg_sender().unwrap());
+    }
+
+    // give up ownership, after which nobody can administer the contract
+    #[storage(read, write)]
+    fn renounce_ownership() {
+        storage.ownership = storage.ownership.renounce(msg_sender().unwrap());
+    }
+
+    // get the current owner
+    #[storage(read)]
+    fn owner() -> Identity {
+        storage.ownership.owner()
+    }
+
+    // get the proposed owner
+    #[storage(read)]
+    fn pending_owner() -> Identity {
+        storage.ownership.pending_owner()
+    }
+
+    // allow an identity to mint up to `allowance` tokens, replacing any previous allowance
+    #[storage(read, write)]
+    fn grant_minter(minter: Identity, allowance: u64) {
+        require_owner();
+        storage.minters.insert(minter, true);
+        storage.minter_allowances.insert(minter, allowance);
+    }
+
+    // stop an identity from minting
+    #[storage(read, write)]
+    fn revoke_minter(minter: Identity) {
+        require_owner();
+        storage.minters.insert(minter, false);
+        storage.minter_allowances.insert(minter, 0);
+    }
+
+    // get the number of tokens a minter can still mint
+    #[storage(read)]
+    fn minter_allowance(minter: Identity) -> u64 {
+        storage.minter_allowances.get(minter)
+    }
+
+    // create a schedule that vests `total` tokens to the beneficiary and reserve them out of the remaining
+    // supply, only the owner can create schedules
+    #[storage(read, write)]
+    fn create_vesting_schedule(beneficiary: Identity, start: u64, cliff: u64, duration: u64, total: u64) {
+        require_owner();
+        require(0 < duration && duration <= MAX_VESTING_DURATION && cliff <= duration && 0 < total, VestingError::InvalidSchedule);
+        require(storage.vesting_schedules.get(beneficiary).duration == 0, VestingError::ScheduleAlreadyExists);
+        require(total <= storage.max_supply - storage.total_supply - storage.reserved_supply, SupplyError::MaxSupplyExceeded);
+
+        storage.reserved_supply = storage.reserved_supply + total;
+
+        storage.vesting_schedules.insert(beneficiary, VestingSchedule {
+            start: start,
+            cliff: cliff,
+            duration: duration,
+            total: total,
+            released: 0,
+        });
+    }
+
+    // mint the tokens that have vested for the caller since their last release
+    //
+    // returns the number of tokens released
+    #[storage(read, write)]
+    fn release() -> u64 {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+
+        let beneficiary = msg_sender().unwrap();
+        let mut schedule = storage.vesting_schedules.get(beneficiary);
+        let amount = schedule.vested(height()) - schedule.released;
+
+        require(0 < amount, VestingError::NothingToRelease);
+
+        schedule.released = schedule.released + amount;
+        storage.vesting_schedules.insert(beneficiary, schedule);
+        mint_reserved(amount, beneficiary);
+
+        amount
+    }
+
+    // get the vesting schedule of a beneficiary
+    #[storage(read)]
+    fn vesting_schedule(beneficiary: Identity) -> VestingSchedule {
+        storage.vesting_schedules.get(beneficiary)
+    }
+
+    // get the number of vested tokens a beneficiary can release right now
+    #[storage(read)]
+    fn releasable(beneficiary: Identity) -> u64 {
+        let schedule = storage.vesting_schedules.get(beneficiary);
+        schedule.vested(height()) - schedule.released
+    }
+
+    // set the merkle root of the claimable airdrop and reserve the `total` it allocates, only the owner
+    // can set it. whatever is left unclaimed from the previous airdrop is released back to the supply
+    #[storage(read, write)]
+    fn set_airdrop_root(root: b256, total: u64) {
+        require_owner();
+
+        let reserved_supply = storage.reserved_supply - storage.airdrop_remaining;
+        require(total <= storage.max_supply - storage.total_supply - reserved_supply, SupplyError::MaxSupplyExceeded);
+
+        storage.reserved_supply = reserved_supply + total;
+        storage.airdrop_remaining = total;
+        storage.airdrop_root = root;
+        storage.airdrop_epoch = storage.airdrop_epoch + 1;
+    }
+
+    // claim the airdrop allocated to the caller at leaf `index` of the merkle tree
+    #[storage(read, write)]
+    fn claim(index: u64, amount: u64, proof: [b256; 16], proof_length: u64) {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        require(proof_length <= MAX_PROOF_LENGTH, AirdropError::ProofTooLong);
+
+        let key = (storage.airdrop_epoch, index / 64);
+        let mask = 1 << (index % 64);
+        let claimed = storage.claimed.get(key);
+        require((claimed & mask) == 0, AirdropError::AlreadyClaimed);
+
+        let user = msg_sender().unwrap();
+
+        // walk from the leaf to the root, the bits of the index tell us which side each sibling is on
+        let mut node = leaf_hash(index, user, amount);
+        let mut position = index;
+        let mut i = 0;
+        while i < proof_length {
+            if position % 2 == 0 {
+                node = sha256((node, proof[i]));
+            } else {
+                node = sha256((proof[i], node));
+            }
+            position = position / 2;
+            i += 1;
+        }
+        require(position == 0 && node == storage.airdrop_root, AirdropError::InvalidProof);
+        require(amount <= storage.airdrop_remaining, AirdropError::AirdropExhausted);
+
+        storage.claimed.insert(key, claimed | mask);
+        storage.airdrop_remaining = storage.airdrop_remaining - amount;
+        mint_reserved(amount, user);
+    }
+
+    // check whether leaf `index` of the current airdrop has already been claimed
+    #[storage(read)]
+    fn is_claimed(index: u64) -> bool {
+        (storage.claimed.get((storage.airdrop_epoch, index / 64)) & (1 << (index % 64))) != 0
+    }
+
+    // get the number of tokens currently in circulation
+    #[storage(read)]
+    fn total_supply() -> u64 {
+        storage.total_supply
+    }
+
+    // get the number of tokens that can ever be in circulation
+    #[storage(read)]
+    fn max_supply() -> u64 {
+        storage.max_supply
+    }
+
+    // get the number of tokens set aside for airdrop claims and vesting schedules
+    #[storage(read)]
+    fn reserved_supply() -> u64 {
+        storage.reserved_supply
+    }
+
+    // burn the tokens forwarded with the call, removing them from circulation
+    #[storage(read, write)]
+    fn burn() {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        require(contract_id() == msg_asset_id(), UserError::IncorrectAssetSent);
+        require(0 < msg_amount(), UserError::AmountCannotBeZero);
+
+        let amount = msg_amount();
+        storage.total_supply = storage.total_supply - amount;
+        burn(amount);
+    }
+
+    // get the name of the token
+    #[storage(read)]
+    fn name() -> str[32] {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.name
+    }
+
+    // get the ticker symbol of the token
+    #[storage(read)]
+    fn symbol() -> str[8] {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.symbol
+    }
+
+    // get the number of decimals used to display token amounts
+    #[storage(read)]
+    fn decimals() -> u8 {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.decimals
+    }
+}
+
+// revert unless the contract is initialized and the caller is the owner
+#[storage(read)]
+fn require_owner() {
+    require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+    storage.ownership.only_owner(msg_sender().unwrap());
+}
+
+// revert unless the caller is a minter with at least `amount` tokens of allowance left, then spend it
+#[storage(read, write)]
+fn spend_minter_allowance(amount: u64) {
+    require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+
+    let minter = msg_sender().unwrap();
+    require(storage.minters.get(minter), AccessError::SenderNotMinter);
+
+    let allowance = storage.minter_allowances.get(minter);
+    require(amount <= allowance, SupplyError::AllowanceExceeded);
+
+    storage.minter_allowances.insert(minter, allowance - amount);
+}
+
+// mint tokens and send them to the recipient, without touching the supply that is reserved for claims
+#[storage(read, write)]
+fn mint_to(amount: u64, recipient: Identity) {
+    require(amount <= storage.max_supply - storage.total_supply - storage.reserved_supply, SupplyError::MaxSupplyExceeded);
+    mint(amount, recipient);
+}
+
+// mint tokens out of the reserved supply, which was checked against the supply cap when it was reserved
+#[storage(read, write)]
+fn mint_reserved(amount: u64, recipient: Identity) {
+    storage.reserved_supply = storage.reserved_supply - amount;
+    mint(amount, recipient);
+}
+
+// mint tokens and send them to the recipient, logging a MintEvent
+#[storage(read, write)]
+fn mint(amount: u64, recipient: Identity) {
+    storage.total_supply = storage.total_supply + amount;
+
+    match recipient {
+        Identity::Address(address) => mint_to_address(amount, address),
+        Identity::ContractId(contract) => mint_to_contract(amount, contract),
+    }
+
+    log(MintEvent {
+        recipient: recipient,
+        amount: amount,
+    });
+}
+
+// hash of an airdrop leaf, the identity is tagged so that an address and a contract id never collide
+fn leaf_hash(index: u64, recipient: Identity, amount: u64) -> b256 {
+    match recipient {
+        Identity::Address(address) => sha256((index, 0, address.value, amount)),
+        Identity::ContractId(contract) => sha256((index, 1, contract.value, amount)),
+    }
 }
@@ -0,0 +1,139 @@
+library token_library;
+
+use core::ops::Eq;
+
+use std::{address::Address, identity::Identity};
+
+abi MyToken {
+    #[storage(read, write)]
+    fn initialize(owner: Identity, name: str[32], symbol: str[8], decimals: u8, max_supply: u64);
+
+    #[storage(read, write)]
+    fn mint_and_send_to_address(amount: u64, recipient: Address) -> bool;
+
+    #[storage(read, write)]
+    fn mint_and_send_to_identity(amount: u64, recipient: Identity) -> bool;
+
+    #[storage(read, write)]
+    fn mint_batch(airdrops: [Airdrop; 10]) -> u64;
+
+    #[storage(read, write)]
+    fn transfer_ownership(new_owner: Identity);
+
+    #[storage(read, write)]
+    fn accept_ownership();
+
+    #[storage(read, write)]
+    fn renounce_ownership();
+
+    #[storage(read)]
+    fn owner() -> Identity;
+
+    #[storage(read)]
+    fn pending_owner() -> Identity;
+
+    #[storage(read, write)]
+    fn grant_minter(minter: Identity, allowance: u64);
+
+    #[storage(read, write)]
+    fn revoke_minter(minter: Identity);
+
+    #[storage(read)]
+    fn minter_allowance(minter: Identity) -> u64;
+
+    #[storage(read, write)]
+    fn create_vesting_schedule(beneficiary: Identity, start: u64, cliff: u64, duration: u64, total: u64);
+
+    #[storage(read, write)]
+    fn release() -> u64;
+
+    #[storage(read)]
+    fn vesting_schedule(beneficiary: Identity) -> VestingSchedule;
+
+    #[storage(read)]
+    fn releasable(beneficiary: Identity) -> u64;
+
+    #[storage(read, write)]
+    fn set_airdrop_root(root: b256, total: u64);
+
+    #[storage(read, write)]
+    fn claim(index: u64, amount: u64, proof: [b256; 16], proof_length: u64);
+
+    #[storage(read)]
+    fn is_claimed(index: u64) -> bool;
+
+    #[storage(read)]
+    fn total_supply() -> u64;
+
+    #[storage(read)]
+    fn max_supply() -> u64;
+
+    #[storage(read)]
+    fn reserved_supply() -> u64;
+
+    #[storage(read, write)]
+    fn burn();
+
+    #[storage(read)]
+    fn name() -> str[32];
+
+    #[storage(read)]
+    fn symbol() -> str[8];
+
+    #[storage(read)]
+    fn decimals() -> u8;
+}
+
+// a single entry of a batch mint, entries with an amount of 0 are skipped
+pub struct Airdrop {
+    recipient: Identity,
+    amount: u64,
+}
+
+// tokens that unlock linearly over `duration` blocks from `start`, with nothing unlocked before `start + cliff`
+pub struct VestingSchedule {
+    start: u64,
+    cliff: u64,
+    duration: u64,
+    total: u64,
+    released: u64,
+}
+
+impl VestingSchedule {
+    // the number of tokens that have unlocked by block `height`, including those already released
+    pub fn vested(self, height: u64) -> u64 {
+        if height < self.start + self.cliff {
+            return 0;
+        }
+
+        let elapsed = height - self.start;
+        if self.duration <= elapsed {
+            return self.total;
+        }
+
+        // total * elapsed / duration, split up so that the product cannot overflow. the remainder and elapsed
+        // are both below duration, which is at most 2^32 - 1, so their product fits in a u64
+        (self.total / self.duration) * elapsed + (self.total % self.duration) * elapsed / self.duration
+    }
+}
+
+// logged whenever tokens are minted
+pub struct MintEvent {
+    recipient: Identity,
+    amount: u64,
+}
+
+pub enum State {
+    NotInitialized: (),
+    Initialized: (),
+}
+
+impl Eq for State {
+    fn eq(self, other: Self) -> bool {
+        match(self, other) {
+            (State::Initialized, State::Initialized) => true,
+            (State::NotInitialized, State::NotInitialized) => true,
+            _ => false, 
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::Identity;
+use fuels::tx::{Address, ContractId, Receipt};
+
+// the ABI does not describe logged types, so this mirrors MintEvent in token_library.sw and is decoded
+// by hand from the LogData receipts
+#[derive(Debug, PartialEq)]
+pub(crate) struct MintEvent {
+    pub(crate) recipient: Identity,
+    pub(crate) amount: u64,
+}
+
+impl MintEvent {
+    // the recipient is an enum, its variant index followed by the 32 bytes of the address or contract id
+    fn decode(data: &[u8]) -> Self {
+        assert_eq!(data.len(), 48, "log is not a MintEvent");
+
+        let word = |offset: usize| u64::from_be_bytes(data[offset..offset + 8].try_into().unwrap());
+        let bits: [u8; 32] = data[8..40].try_into().unwrap();
+
+        let recipient = match word(0) {
+            0 => Identity::Address(Address::from(bits)),
+            1 => Identity::ContractId(ContractId::from(bits)),
+            variant => panic!("unknown identity variant: {}", variant),
+        };
+
+        Self {
+            recipient,
+            amount: word(40),
+        }
+    }
+}
+
+// decode the MintEvents logged during a call, in the order they were logged
+pub(crate) fn mint_events(receipts: &[Receipt]) -> Vec<MintEvent> {
+    receipts
+        .iter()
+        .filter_map(|receipt| match receipt {
+            Receipt::LogData { data, .. } => Some(MintEvent::decode(data)),
+            _ => None,
+        })
+        .collect()
+}
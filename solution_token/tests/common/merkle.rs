@@ -0,0 +1,127 @@
+use fuels::tx::{Address, ContractId};
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+
+// must match MAX_PROOF_LENGTH in the token contract
+pub(crate) const MAX_PROOF_LENGTH: usize = 16;
+
+// mirrors the `Identity` of the leaf in the token contract, which tags addresses and contracts
+pub(crate) enum Recipient {
+    Address(Address),
+    ContractId(ContractId),
+}
+
+pub(crate) struct AirdropLeaf {
+    pub(crate) recipient: Recipient,
+    pub(crate) amount: u64,
+}
+
+// a merkle tree over the airdrop leaves, stored level by level from the leaves up to the root
+pub(crate) struct AirdropTree {
+    pub(crate) leaves: Vec<AirdropLeaf>,
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl AirdropTree {
+    // build the tree from CSV text with a `kind,identity,amount` header, where kind is
+    // `address` or `contract`. the index of each leaf is its row number, starting at 0
+    pub(crate) fn from_csv(csv: &str) -> Self {
+        let leaves = csv
+            .lines()
+            .skip(1)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+                assert_eq!(fields.len(), 3, "malformed airdrop row: {}", line);
+
+                let recipient = match fields[0] {
+                    "address" => Recipient::Address(Address::from_str(fields[1]).unwrap()),
+                    "contract" => Recipient::ContractId(ContractId::from_str(fields[1]).unwrap()),
+                    kind => panic!("unknown identity kind: {}", kind),
+                };
+                let amount = fields[2].parse().unwrap();
+
+                AirdropLeaf { recipient, amount }
+            })
+            .collect();
+
+        Self::new(leaves)
+    }
+
+    pub(crate) fn from_csv_file(path: &str) -> Self {
+        Self::from_csv(&std::fs::read_to_string(path).unwrap())
+    }
+
+    pub(crate) fn new(leaves: Vec<AirdropLeaf>) -> Self {
+        assert!(!leaves.is_empty(), "an airdrop needs at least one leaf");
+
+        let mut levels = vec![leaves
+            .iter()
+            .enumerate()
+            .map(|(index, leaf)| leaf_hash(index as u64, &leaf.recipient, leaf.amount))
+            .collect::<Vec<_>>()];
+
+        while levels.last().unwrap().len() > 1 {
+            let level = levels.last().unwrap();
+            // an odd node out is paired with itself
+            let parents = level
+                .chunks(2)
+                .map(|pair| node_hash(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+                .collect();
+            levels.push(parents);
+        }
+
+        assert!(
+            levels.len() - 1 <= MAX_PROOF_LENGTH,
+            "too many leaves for the proof length supported by the contract"
+        );
+
+        Self { leaves, levels }
+    }
+
+    pub(crate) fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    // the number of tokens the contract has to reserve for the airdrop
+    pub(crate) fn total(&self) -> u64 {
+        self.leaves.iter().map(|leaf| leaf.amount).sum()
+    }
+
+    // the proof for leaf `index`, padded to MAX_PROOF_LENGTH, along with its real length
+    pub(crate) fn proof(&self, index: usize) -> ([[u8; 32]; MAX_PROOF_LENGTH], u64) {
+        let mut proof = [[0u8; 32]; MAX_PROOF_LENGTH];
+        let mut position = index;
+
+        let depth = self.levels.len() - 1;
+        for (level, slot) in self.levels[..depth].iter().zip(proof.iter_mut()) {
+            let sibling = position ^ 1;
+            *slot = *level.get(sibling).unwrap_or(&level[position]);
+            position /= 2;
+        }
+
+        (proof, depth as u64)
+    }
+}
+
+// matches `leaf_hash` in the token contract: sha256 of the (index, kind, identity, amount) tuple
+pub(crate) fn leaf_hash(index: u64, recipient: &Recipient, amount: u64) -> [u8; 32] {
+    let (kind, bits): (u64, [u8; 32]) = match recipient {
+        Recipient::Address(address) => (0, **address),
+        Recipient::ContractId(contract) => (1, **contract),
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(index.to_be_bytes());
+    hasher.update(kind.to_be_bytes());
+    hasher.update(bits);
+    hasher.update(amount.to_be_bytes());
+    hasher.finalize().into()
+}
+
+pub(crate) fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
@@ -1,31 +1,1121 @@
-use fuels::{prelude::*, tx::ContractId};
+#![allow(dead_code)]
+
+#[path = "common/events.rs"]
+mod events;
+#[path = "common/merkle.rs"]
+mod merkle;
+
+use events::*;
+use fuels::{
+    prelude::*,
+    signers::fuel_crypto::Hasher,
+    tx::{Bytes32, ContractId, Receipt, StorageSlot},
+};
+use merkle::*;
 
 // Load abi from json
 abigen!(MyContract, "out/debug/token-abi.json");
 
-async fn get_contract_instance() -> (MyContract, ContractId) {
+const NAME: &str = "Favorite Number Governance Token";
+const SYMBOL: &str = "FAVNUM  ";
+const DECIMALS: u8 = 9;
+const MAX_SUPPLY: u64 = 1_000_000_000;
+const MAX_BATCH_SIZE: usize = 10;
+
+struct User {
+    token_handle: MyContract,
+    wallet: LocalWallet,
+}
+
+async fn get_contract_instance() -> (ContractId, [User; 2]) {
     // Launch a local network and deploy the contract
-    let wallet = launch_provider_and_get_wallet().await;
+    let num_wallets = 2;
+    let coins_per_wallet = 1;
+    let amount_per_coin = 1_000_000;
+    let config = WalletsConfig::new(
+        Some(num_wallets),
+        Some(coins_per_wallet),
+        Some(amount_per_coin),
+    );
+    let mut wallets = launch_custom_provider_and_get_wallets(config, None).await;
+    let owner_wallet = wallets.pop().unwrap();
+    let user_wallet = wallets.pop().unwrap();
 
+    // the owner is the admin that the contract expects to initialize it
     let id = Contract::deploy(
         "./out/debug/token.bin",
-        &wallet,
+        &owner_wallet,
+        TxParameters::default(),
+        storage_with_admin(owner_wallet.address()),
+    )
+    .await
+    .unwrap();
+
+    let owner = User {
+        token_handle: MyContract::new(id.to_string(), owner_wallet.clone()),
+        wallet: owner_wallet,
+    };
+    let user = User {
+        token_handle: MyContract::new(id.to_string(), user_wallet.clone()),
+        wallet: user_wallet,
+    };
+
+    (id, [owner, user])
+}
+
+// the storage slots of the contract with `admin` written into the slot of its `admin` field, which is the
+// first field of its storage block
+fn storage_with_admin(admin: Address) -> StorageConfiguration {
+    let admin_key = Hasher::hash(b"storage_0");
+
+    // replace the default slot rather than adding a second one with the same key
+    let slots = std::fs::read_to_string("./out/debug/token-storage_slots.json").unwrap();
+    let mut slots: Vec<StorageSlot> = serde_json::from_str(&slots).unwrap();
+    slots.retain(|slot| *slot.key() != admin_key);
+    slots.push(StorageSlot::new(admin_key, Bytes32::from(*admin)));
+    // transactions need their storage slots in order
+    slots.sort_by_key(|slot| *slot.key());
+
+    StorageConfiguration::new(None, Some(slots))
+}
+
+async fn initialize(token_handle: &MyContract, owner: Address) {
+    initialize_with_max_supply(token_handle, owner, MAX_SUPPLY).await;
+}
+
+// initializes the contract and makes the owner a minter that can mint the whole supply
+async fn initialize_with_max_supply(token_handle: &MyContract, owner: Address, max_supply: u64) {
+    token_handle
+        .initialize(
+            Identity::Address(owner),
+            NAME.to_string(),
+            SYMBOL.to_string(),
+            DECIMALS,
+            max_supply,
+        )
+        .call()
+        .await
+        .unwrap();
+
+    grant_minter(token_handle, Identity::Address(owner), max_supply).await;
+}
+
+async fn transfer_ownership(token_handle: &MyContract, new_owner: Identity) {
+    token_handle
+        .transfer_ownership(new_owner)
+        .call()
+        .await
+        .unwrap();
+}
+
+async fn accept_ownership(token_handle: &MyContract) {
+    token_handle.accept_ownership().call().await.unwrap();
+}
+
+async fn renounce_ownership(token_handle: &MyContract) {
+    token_handle.renounce_ownership().call().await.unwrap();
+}
+
+async fn owner(token_handle: &MyContract) -> Identity {
+    token_handle.owner().call().await.unwrap().value
+}
+
+async fn pending_owner(token_handle: &MyContract) -> Identity {
+    token_handle.pending_owner().call().await.unwrap().value
+}
+
+async fn grant_minter(token_handle: &MyContract, minter: Identity, allowance: u64) {
+    token_handle
+        .grant_minter(minter, allowance)
+        .call()
+        .await
+        .unwrap();
+}
+
+async fn revoke_minter(token_handle: &MyContract, minter: Identity) {
+    token_handle.revoke_minter(minter).call().await.unwrap();
+}
+
+async fn minter_allowance(token_handle: &MyContract, minter: Identity) -> u64 {
+    token_handle
+        .minter_allowance(minter)
+        .call()
+        .await
+        .unwrap()
+        .value
+}
+
+async fn mint_and_send_to_address(
+    token_handle: &MyContract,
+    amount: u64,
+    recipient: Address,
+) -> Vec<Receipt> {
+    token_handle
+        .mint_and_send_to_address(amount, recipient)
+        .append_variable_outputs(1)
+        .call()
+        .await
+        .unwrap()
+        .receipts
+}
+
+async fn mint_and_send_to_identity(
+    token_handle: &MyContract,
+    amount: u64,
+    recipient: Identity,
+) -> Vec<Receipt> {
+    let call = token_handle.mint_and_send_to_identity(amount, recipient.clone());
+
+    // coins sent to an address need a variable output, a contract has to be an input of the transaction
+    let call = match recipient {
+        Identity::Address(_) => call.append_variable_outputs(1),
+        Identity::ContractId(contract_id) => call.set_contracts(&[contract_id]),
+    };
+
+    call.call().await.unwrap().receipts
+}
+
+// mint to every recipient in `airdrops`, splitting them into as many transactions as needed
+//
+// returns the result of each transaction, in order, so that a failed chunk can be retried
+async fn mint_in_batches(
+    token_handle: &MyContract,
+    airdrops: &[(Identity, u64)],
+) -> Vec<Result<u64, Error>> {
+    let mut results = vec![];
+
+    for chunk in airdrops.chunks(MAX_BATCH_SIZE) {
+        let mut batch = [(); MAX_BATCH_SIZE].map(|_| Airdrop {
+            recipient: Identity::Address(Address::zeroed()),
+            amount: 0,
+        });
+        for (slot, (recipient, amount)) in batch.iter_mut().zip(chunk) {
+            slot.recipient = recipient.clone();
+            slot.amount = *amount;
+        }
+
+        // coins sent to addresses need a variable output, contracts have to be inputs of the transaction
+        let num_outputs = chunk
+            .iter()
+            .filter(|(recipient, _)| matches!(recipient, Identity::Address(_)))
+            .count();
+        // a contract can only be an input once, however many of the airdrops it receives
+        let mut contracts: Vec<ContractId> = chunk
+            .iter()
+            .filter_map(|(recipient, _)| match recipient {
+                Identity::ContractId(contract_id) => Some(*contract_id),
+                Identity::Address(_) => None,
+            })
+            .collect();
+        contracts.sort();
+        contracts.dedup();
+
+        let result = token_handle
+            .mint_batch(batch)
+            .append_variable_outputs(num_outputs as u64)
+            .set_contracts(&contracts)
+            .call()
+            .await
+            .map(|response| response.value);
+        results.push(result);
+    }
+
+    results
+}
+
+// deploy another instance of the token contract to receive tokens, each `salt` gives a different contract
+async fn deploy_recipient_contract(wallet: &LocalWallet, salt: u8) -> ContractId {
+    Contract::deploy_with_salt(
+        "./out/debug/token.bin",
+        wallet,
         TxParameters::default(),
         StorageConfiguration::with_storage_path(Some(
             "./out/debug/token-storage_slots.json".to_string(),
         )),
+        Salt::from([salt; 32]),
     )
     .await
-    .unwrap();
+    .unwrap()
+}
+
+async fn set_airdrop_root(token_handle: &MyContract, tree: &AirdropTree) {
+    token_handle
+        .set_airdrop_root(tree.root(), tree.total())
+        .call()
+        .await
+        .unwrap();
+}
+
+async fn claim(token_handle: &MyContract, tree: &AirdropTree, index: usize) {
+    let (proof, proof_length) = tree.proof(index);
+    token_handle
+        .claim(index as u64, tree.leaves[index].amount, proof, proof_length)
+        .append_variable_outputs(1)
+        .call()
+        .await
+        .unwrap();
+}
+
+// an airdrop of `amounts[i]` to `recipients[i]`, written out as CSV the same way a launch list would be
+fn airdrop_csv(recipients: &[Address], amounts: &[u64]) -> String {
+    let mut csv = "kind,identity,amount\n".to_string();
+    for (recipient, amount) in recipients.iter().zip(amounts) {
+        csv.push_str(&format!("address,0x{},{}\n", recipient, amount));
+    }
+    csv
+}
+
+async fn total_supply(token_handle: &MyContract) -> u64 {
+    token_handle.total_supply().call().await.unwrap().value
+}
+
+async fn reserved_supply(token_handle: &MyContract) -> u64 {
+    token_handle.reserved_supply().call().await.unwrap().value
+}
+
+async fn burn(token_handle: &MyContract, token_contract_id: ContractId, amount: u64) {
+    let call_params = CallParameters::new(
+        Some(amount),                            // amount
+        Some(AssetId::from(*token_contract_id)), // asset ID
+        Some(1_000_000),                         // gas forwarded
+    );
+    token_handle
+        .burn()
+        .call_params(call_params)
+        .call()
+        .await
+        .unwrap();
+}
+
+async fn create_vesting_schedule(
+    token_handle: &MyContract,
+    beneficiary: Identity,
+    start: u64,
+    cliff: u64,
+    duration: u64,
+    total: u64,
+) {
+    token_handle
+        .create_vesting_schedule(beneficiary, start, cliff, duration, total)
+        .call()
+        .await
+        .unwrap();
+}
+
+async fn release(token_handle: &MyContract) -> u64 {
+    token_handle
+        .release()
+        .append_variable_outputs(1)
+        .call()
+        .await
+        .unwrap()
+        .value
+}
+
+async fn block_height(wallet: &LocalWallet) -> u64 {
+    wallet
+        .get_provider()
+        .unwrap()
+        .chain_info()
+        .await
+        .unwrap()
+        .latest_block
+        .height
+        .0
+}
+
+async fn advance_blocks(wallet: &LocalWallet, amount: u64) {
+    wallet
+        .get_provider()
+        .unwrap()
+        .produce_blocks(amount)
+        .await
+        .unwrap();
+}
 
-    let instance = MyContract::new(id.to_string(), wallet);
+// reference implementation of `VestingSchedule::vested` in the token contract
+fn vested(start: u64, cliff: u64, duration: u64, total: u64, height: u64) -> u64 {
+    if height < start + cliff {
+        0
+    } else if start + duration <= height {
+        total
+    } else {
+        total * (height - start) / duration
+    }
+}
 
-    (instance, id)
+async fn get_token_balance_in_wallet(token_contract_id: ContractId, wallet: &LocalWallet) -> u64 {
+    wallet
+        .get_asset_balance(&AssetId::from(*token_contract_id))
+        .await
+        .unwrap()
 }
 
 #[tokio::test]
 async fn can_get_contract_id() {
-    let (_instance, _id) = get_contract_instance().await;
+    let (_id, _users) = get_contract_instance().await;
 
     // Now you have an instance of your contract you can use to test each function
 }
+
+#[cfg(test)]
+mod should_pass {
+    use super::*;
+
+    #[tokio::test]
+    async fn owner_can_mint() {
+        let (id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        mint_and_send_to_address(&owner.token_handle, 1_000_000, user.wallet.address()).await;
+
+        assert_eq!(
+            get_token_balance_in_wallet(id, &user.wallet).await,
+            1_000_000
+        );
+    }
+
+    #[tokio::test]
+    async fn minting_logs_an_event() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let receipts =
+            mint_and_send_to_address(&owner.token_handle, 1_000_000, user.wallet.address()).await;
+        assert_eq!(
+            mint_events(&receipts),
+            vec![MintEvent {
+                recipient: Identity::Address(user.wallet.address()),
+                amount: 1_000_000,
+            }]
+        );
+
+        let contract = Identity::ContractId(deploy_recipient_contract(&owner.wallet, 1).await);
+        let receipts = mint_and_send_to_identity(&owner.token_handle, 500, contract.clone()).await;
+        assert_eq!(
+            mint_events(&receipts),
+            vec![MintEvent {
+                recipient: contract,
+                amount: 500,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn can_read_metadata() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        assert_eq!(user.token_handle.name().call().await.unwrap().value, NAME);
+        assert_eq!(
+            user.token_handle.symbol().call().await.unwrap().value,
+            SYMBOL
+        );
+        assert_eq!(
+            user.token_handle.decimals().call().await.unwrap().value,
+            DECIMALS
+        );
+    }
+
+    #[tokio::test]
+    async fn ownership_is_transferred_in_two_steps() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let new_owner = Identity::Address(user.wallet.address());
+        transfer_ownership(&owner.token_handle, new_owner.clone()).await;
+
+        // nothing changes until the new owner accepts
+        assert_eq!(
+            owner(&owner.token_handle).await,
+            Identity::Address(owner.wallet.address())
+        );
+        assert_eq!(pending_owner(&owner.token_handle).await, new_owner);
+
+        accept_ownership(&user.token_handle).await;
+
+        assert_eq!(owner(&owner.token_handle).await, new_owner);
+
+        // the new owner can administer the contract
+        grant_minter(&user.token_handle, new_owner, 100).await;
+    }
+
+    #[tokio::test]
+    async fn minters_spend_their_allowance() {
+        let (id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let minter = Identity::Address(user.wallet.address());
+        grant_minter(&owner.token_handle, minter.clone(), 1_000_000).await;
+
+        mint_and_send_to_address(&user.token_handle, 400_000, user.wallet.address()).await;
+        mint_and_send_to_address(&user.token_handle, 600_000, owner.wallet.address()).await;
+
+        assert_eq!(minter_allowance(&owner.token_handle, minter).await, 0);
+        assert_eq!(get_token_balance_in_wallet(id, &user.wallet).await, 400_000);
+        assert_eq!(
+            get_token_balance_in_wallet(id, &owner.wallet).await,
+            600_000
+        );
+    }
+
+    #[tokio::test]
+    async fn granting_replaces_the_allowance() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let minter = Identity::Address(user.wallet.address());
+        grant_minter(&owner.token_handle, minter.clone(), 1_000_000).await;
+        mint_and_send_to_address(&user.token_handle, 400_000, user.wallet.address()).await;
+        grant_minter(&owner.token_handle, minter.clone(), 50).await;
+
+        assert_eq!(minter_allowance(&owner.token_handle, minter).await, 50);
+    }
+
+    #[tokio::test]
+    async fn owner_can_mint_to_an_address_identity() {
+        let (id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        mint_and_send_to_identity(
+            &owner.token_handle,
+            1_000_000,
+            Identity::Address(user.wallet.address()),
+        )
+        .await;
+
+        assert_eq!(
+            get_token_balance_in_wallet(id, &user.wallet).await,
+            1_000_000
+        );
+    }
+
+    #[tokio::test]
+    async fn owner_can_mint_to_a_contract() {
+        let (id, [owner, _]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let recipient_id = deploy_recipient_contract(&owner.wallet, 1).await;
+
+        mint_and_send_to_identity(
+            &owner.token_handle,
+            1_000_000,
+            Identity::ContractId(recipient_id),
+        )
+        .await;
+
+        let balance = owner
+            .wallet
+            .get_provider()
+            .unwrap()
+            .get_contract_asset_balance(&recipient_id, AssetId::from(*id))
+            .await
+            .unwrap();
+        assert_eq!(balance, 1_000_000);
+        assert_eq!(total_supply(&owner.token_handle).await, 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn owner_can_mint_in_batches() {
+        let (id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        // 25 airdrops split into chunks of 10, 10 and 5
+        let airdrops: Vec<(Identity, u64)> = (0..25)
+            .map(|i| {
+                let recipient = if i % 2 == 0 {
+                    user.wallet.address()
+                } else {
+                    owner.wallet.address()
+                };
+                (Identity::Address(recipient), 1_000)
+            })
+            .collect();
+
+        let results = mint_in_batches(&owner.token_handle, &airdrops).await;
+
+        let minted: Vec<u64> = results.into_iter().map(|result| result.unwrap()).collect();
+        assert_eq!(minted, vec![10, 10, 5]);
+        assert_eq!(get_token_balance_in_wallet(id, &user.wallet).await, 13_000);
+        assert_eq!(get_token_balance_in_wallet(id, &owner.wallet).await, 12_000);
+        assert_eq!(total_supply(&owner.token_handle).await, 25_000);
+    }
+
+    #[tokio::test]
+    async fn owner_can_mint_in_batches_to_contracts() {
+        let (id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let first = deploy_recipient_contract(&owner.wallet, 1).await;
+        let second = deploy_recipient_contract(&owner.wallet, 2).await;
+
+        // a chunk that mixes an address with two contracts, one of which receives twice
+        let airdrops = vec![
+            (Identity::ContractId(first), 1_000),
+            (Identity::Address(user.wallet.address()), 2_000),
+            (Identity::ContractId(second), 3_000),
+            (Identity::ContractId(first), 4_000),
+        ];
+
+        let results = mint_in_batches(&owner.token_handle, &airdrops).await;
+
+        assert_eq!(*results[0].as_ref().unwrap(), 4);
+        let provider = owner.wallet.get_provider().unwrap();
+        for (contract_id, balance) in [(first, 5_000), (second, 3_000)] {
+            assert_eq!(
+                provider
+                    .get_contract_asset_balance(&contract_id, AssetId::from(*id))
+                    .await
+                    .unwrap(),
+                balance
+            );
+        }
+        assert_eq!(get_token_balance_in_wallet(id, &user.wallet).await, 2_000);
+    }
+
+    #[tokio::test]
+    async fn batch_reports_failed_chunks() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize_with_max_supply(&owner.token_handle, owner.wallet.address(), 15_000).await;
+
+        // the second chunk would exceed the supply cap
+        let airdrops: Vec<(Identity, u64)> = (0..20)
+            .map(|_| (Identity::Address(user.wallet.address()), 1_000))
+            .collect();
+
+        let results = mint_in_batches(&owner.token_handle, &airdrops).await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(*results[0].as_ref().unwrap(), 10);
+        assert!(results[1].is_err());
+        assert_eq!(total_supply(&owner.token_handle).await, 10_000);
+    }
+
+    #[tokio::test]
+    async fn users_can_claim_the_airdrop() {
+        let (id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let tree = AirdropTree::from_csv(&airdrop_csv(
+            &[
+                owner.wallet.address(),
+                user.wallet.address(),
+                Address::zeroed(),
+            ],
+            &[100, 200, 300],
+        ));
+        set_airdrop_root(&owner.token_handle, &tree).await;
+        assert_eq!(reserved_supply(&owner.token_handle).await, 600);
+
+        claim(&user.token_handle, &tree, 1).await;
+        claim(&owner.token_handle, &tree, 0).await;
+
+        assert_eq!(get_token_balance_in_wallet(id, &user.wallet).await, 200);
+        assert_eq!(get_token_balance_in_wallet(id, &owner.wallet).await, 100);
+        assert!(user.token_handle.is_claimed(1).call().await.unwrap().value);
+        assert!(!user.token_handle.is_claimed(2).call().await.unwrap().value);
+        assert_eq!(total_supply(&owner.token_handle).await, 300);
+        assert_eq!(reserved_supply(&owner.token_handle).await, 300);
+    }
+
+    #[tokio::test]
+    async fn each_airdrop_can_be_claimed_at_the_same_index() {
+        let (id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let first = AirdropTree::from_csv(&airdrop_csv(&[user.wallet.address()], &[100]));
+        set_airdrop_root(&owner.token_handle, &first).await;
+        claim(&user.token_handle, &first, 0).await;
+
+        // the new airdrop starts with nothing claimed, so leaf 0 can be claimed again
+        let second = AirdropTree::from_csv(&airdrop_csv(&[user.wallet.address()], &[200]));
+        set_airdrop_root(&owner.token_handle, &second).await;
+        assert!(!user.token_handle.is_claimed(0).call().await.unwrap().value);
+        claim(&user.token_handle, &second, 0).await;
+
+        assert!(user.token_handle.is_claimed(0).call().await.unwrap().value);
+        assert_eq!(get_token_balance_in_wallet(id, &user.wallet).await, 300);
+        assert_eq!(total_supply(&owner.token_handle).await, 300);
+    }
+
+    #[tokio::test]
+    async fn vesting_releases_linearly_after_the_cliff() {
+        let (id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let start = block_height(&owner.wallet).await;
+        let (cliff, duration, total) = (10, 100, 1_000_000);
+        create_vesting_schedule(
+            &owner.token_handle,
+            Identity::Address(user.wallet.address()),
+            start,
+            cliff,
+            duration,
+            total,
+        )
+        .await;
+
+        // halfway through the schedule
+        advance_blocks(&owner.wallet, 50).await;
+        let first = release(&user.token_handle).await;
+        let height = block_height(&owner.wallet).await;
+        assert_eq!(first, vested(start, cliff, duration, total, height));
+
+        // the remainder unlocks once the duration has passed
+        advance_blocks(&owner.wallet, duration).await;
+        let second = release(&user.token_handle).await;
+        assert_eq!(first + second, total);
+
+        assert_eq!(get_token_balance_in_wallet(id, &user.wallet).await, total);
+        assert_eq!(total_supply(&owner.token_handle).await, total);
+        assert_eq!(reserved_supply(&owner.token_handle).await, 0);
+    }
+
+    #[tokio::test]
+    async fn nothing_is_releasable_before_the_cliff() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let beneficiary = Identity::Address(user.wallet.address());
+        let start = block_height(&owner.wallet).await;
+        create_vesting_schedule(
+            &owner.token_handle,
+            beneficiary.clone(),
+            start,
+            50,
+            100,
+            1_000,
+        )
+        .await;
+
+        advance_blocks(&owner.wallet, 10).await;
+
+        let releasable = owner
+            .token_handle
+            .releasable(beneficiary)
+            .call()
+            .await
+            .unwrap()
+            .value;
+        assert_eq!(releasable, 0);
+    }
+
+    #[tokio::test]
+    async fn minting_increases_total_supply() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        assert_eq!(total_supply(&user.token_handle).await, 0);
+
+        mint_and_send_to_address(&owner.token_handle, 1_000_000, user.wallet.address()).await;
+        mint_and_send_to_address(&owner.token_handle, 500_000, owner.wallet.address()).await;
+
+        assert_eq!(total_supply(&user.token_handle).await, 1_500_000);
+    }
+
+    #[tokio::test]
+    async fn owner_can_mint_up_to_max_supply() {
+        let (id, [owner, user]) = get_contract_instance().await;
+
+        initialize_with_max_supply(&owner.token_handle, owner.wallet.address(), 1_000_000).await;
+
+        mint_and_send_to_address(&owner.token_handle, 400_000, user.wallet.address()).await;
+        mint_and_send_to_address(&owner.token_handle, 600_000, user.wallet.address()).await;
+
+        assert_eq!(
+            get_token_balance_in_wallet(id, &user.wallet).await,
+            1_000_000
+        );
+        assert_eq!(
+            owner.token_handle.max_supply().call().await.unwrap().value,
+            1_000_000
+        );
+    }
+
+    #[tokio::test]
+    async fn burning_frees_up_supply() {
+        let (id, [owner, user]) = get_contract_instance().await;
+
+        initialize_with_max_supply(&owner.token_handle, owner.wallet.address(), 1_000_000).await;
+
+        mint_and_send_to_address(&owner.token_handle, 1_000_000, user.wallet.address()).await;
+        burn(&user.token_handle, id, 250_000).await;
+        mint_and_send_to_address(&owner.token_handle, 250_000, user.wallet.address()).await;
+
+        assert_eq!(total_supply(&user.token_handle).await, 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn holders_can_burn() {
+        let (id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        mint_and_send_to_address(&owner.token_handle, 1_000_000, user.wallet.address()).await;
+
+        burn(&user.token_handle, id, 400_000).await;
+
+        assert_eq!(get_token_balance_in_wallet(id, &user.wallet).await, 600_000);
+        assert_eq!(total_supply(&user.token_handle).await, 600_000);
+    }
+}
+
+#[cfg(test)]
+mod revert {
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_non_owner_mints() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        mint_and_send_to_address(&user.token_handle, 1_000_000, user.wallet.address()).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_non_owner_mints_to_an_identity() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        mint_and_send_to_identity(
+            &user.token_handle,
+            1_000_000,
+            Identity::Address(user.wallet.address()),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_minter_exceeds_allowance() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        grant_minter(
+            &owner.token_handle,
+            Identity::Address(user.wallet.address()),
+            100,
+        )
+        .await;
+
+        mint_and_send_to_address(&user.token_handle, 101, user.wallet.address()).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_revoked_minter_mints() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let minter = Identity::Address(user.wallet.address());
+        grant_minter(&owner.token_handle, minter.clone(), 100).await;
+        revoke_minter(&owner.token_handle, minter).await;
+
+        mint_and_send_to_address(&user.token_handle, 1, user.wallet.address()).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_non_owner_grants_minter() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        grant_minter(
+            &user.token_handle,
+            Identity::Address(user.wallet.address()),
+            100,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_non_owner_transfers_ownership() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        transfer_ownership(&user.token_handle, Identity::Address(user.wallet.address())).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_non_pending_owner_accepts_ownership() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        // a typo in the new owner cannot take effect on its own
+        transfer_ownership(&owner.token_handle, Identity::Address(Address::zeroed())).await;
+        accept_ownership(&user.token_handle).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_renounced_owner_grants_minter() {
+        let (_id, [owner, _]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        renounce_ownership(&owner.token_handle).await;
+
+        grant_minter(
+            &owner.token_handle,
+            Identity::Address(owner.wallet.address()),
+            100,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_minting_before_initialization() {
+        let (_id, [owner, _]) = get_contract_instance().await;
+
+        mint_and_send_to_address(&owner.token_handle, 1_000_000, owner.wallet.address()).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_non_admin_initializes() {
+        let (_id, [_, user]) = get_contract_instance().await;
+
+        // the user tries to make themselves owner before the deployer initializes the contract
+        initialize(&user.token_handle, user.wallet.address()).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_reinitializing() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+        initialize(&owner.token_handle, user.wallet.address()).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_minting_past_max_supply() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize_with_max_supply(&owner.token_handle, owner.wallet.address(), 1_000_000).await;
+
+        mint_and_send_to_address(&owner.token_handle, 600_000, user.wallet.address()).await;
+        mint_and_send_to_address(&owner.token_handle, 400_001, user.wallet.address()).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_claiming_with_an_invalid_proof() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let tree = AirdropTree::from_csv(&airdrop_csv(
+            &[owner.wallet.address(), user.wallet.address()],
+            &[100, 200],
+        ));
+        set_airdrop_root(&owner.token_handle, &tree).await;
+
+        // the user claims the owner's leaf
+        claim(&user.token_handle, &tree, 0).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_claiming_more_than_allocated() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let tree = AirdropTree::from_csv(&airdrop_csv(
+            &[owner.wallet.address(), user.wallet.address()],
+            &[100, 200],
+        ));
+        set_airdrop_root(&owner.token_handle, &tree).await;
+
+        let (proof, proof_length) = tree.proof(1);
+        user.token_handle
+            .claim(1, 201, proof, proof_length)
+            .append_variable_outputs(1)
+            .call()
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_claiming_twice() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let tree = AirdropTree::from_csv(&airdrop_csv(
+            &[owner.wallet.address(), user.wallet.address()],
+            &[100, 200],
+        ));
+        set_airdrop_root(&owner.token_handle, &tree).await;
+
+        claim(&user.token_handle, &tree, 1).await;
+        claim(&user.token_handle, &tree, 1).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_non_owner_sets_the_airdrop_root() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let tree = AirdropTree::from_csv(&airdrop_csv(&[user.wallet.address()], &[100]));
+        set_airdrop_root(&user.token_handle, &tree).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_minting_into_the_supply_reserved_for_the_airdrop() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize_with_max_supply(&owner.token_handle, owner.wallet.address(), 1_000_000).await;
+
+        let tree = AirdropTree::from_csv(&airdrop_csv(&[user.wallet.address()], &[400_000]));
+        set_airdrop_root(&owner.token_handle, &tree).await;
+
+        mint_and_send_to_address(&owner.token_handle, 600_001, user.wallet.address()).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_the_airdrop_exceeds_the_remaining_supply() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize_with_max_supply(&owner.token_handle, owner.wallet.address(), 1_000_000).await;
+
+        mint_and_send_to_address(&owner.token_handle, 600_000, user.wallet.address()).await;
+
+        let tree = AirdropTree::from_csv(&airdrop_csv(&[user.wallet.address()], &[400_001]));
+        set_airdrop_root(&owner.token_handle, &tree).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_releasing_before_the_cliff() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        let start = block_height(&owner.wallet).await;
+        create_vesting_schedule(
+            &owner.token_handle,
+            Identity::Address(user.wallet.address()),
+            start,
+            50,
+            100,
+            1_000,
+        )
+        .await;
+
+        release(&user.token_handle).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_releasing_without_a_schedule() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        release(&user.token_handle).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_cliff_is_longer_than_duration() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        create_vesting_schedule(
+            &owner.token_handle,
+            Identity::Address(user.wallet.address()),
+            0,
+            101,
+            100,
+            1_000,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_minting_into_the_supply_reserved_for_vesting() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize_with_max_supply(&owner.token_handle, owner.wallet.address(), 1_000_000).await;
+
+        let start = block_height(&owner.wallet).await;
+        create_vesting_schedule(
+            &owner.token_handle,
+            Identity::Address(user.wallet.address()),
+            start,
+            0,
+            100,
+            600_000,
+        )
+        .await;
+
+        // the vesting tokens are reserved, so only 400_000 are left to mint
+        mint_and_send_to_address(&owner.token_handle, 400_001, user.wallet.address()).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_vesting_duration_is_too_long() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        create_vesting_schedule(
+            &owner.token_handle,
+            Identity::Address(user.wallet.address()),
+            0,
+            0,
+            1 << 32,
+            1_000,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_burning_another_asset() {
+        let (_id, [owner, user]) = get_contract_instance().await;
+
+        initialize(&owner.token_handle, owner.wallet.address()).await;
+
+        burn(&user.token_handle, ContractId::from(*BASE_ASSET_ID), 1).await;
+    }
+}
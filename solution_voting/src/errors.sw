@@ -3,11 +3,36 @@ library errors;
 pub enum InitializationError {
     CannotReinitialize: (),
     ContractNotInitialized: (),
+    ProposalBondCannotBeZero: (),
+    SenderNotAdmin: (),
+}
+
+pub enum RoundError {
+    InvalidWindow: (),
+    NoPendingCall: (),
+    RoundAlreadyStarted: (),
+    RoundNotScheduled: (),
+    RunoffInProgress: (),
+    VotingClosed: (),
+    VotingNotOpen: (),
+    VotingStillOpen: (),
 }
 
 pub enum UserError {
+    AlreadyProposed: (),
     AmountCannotBeZero: (),
+    ClaimPeriodNotOver: (),
+    ClaimPeriodOver: (),
     IncorrectAssetSent: (),
     InvalidNumber: (),
     InsufficientBalance: (),
+    InsufficientVotes: (),
+    NoRewardToClaim: (),
+    NoRewardToSweep: (),
+    NoVotesToUnlock: (),
+    ProposalEliminated: (),
+    RewardAlreadyClaimed: (),
+    RoundNotOver: (),
+    TooManyProposals: (),
+    TooManyProposalsFromSender: (),
 }
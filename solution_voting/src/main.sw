@@ -5,12 +5,16 @@ dep errors;
 
 use voting_library::*;
 use errors::*;
+use ownership::*;
 
 use std::{
+    address::Address,
+    block::height,
     contract_id::ContractId,
     revert::require,
     storage::StorageMap,
     identity::Identity,
+    logging::log,
     context::{call_frames::msg_asset_id, msg_amount, this_balance},
     chain::auth::msg_sender,
     result::*,
@@ -18,12 +22,39 @@ use std::{
     token::transfer,
 };
 
-const MAX_NUM = 100;
+// bounds the work done by execute, which goes through every proposal of the round
+const MAX_PROPOSALS = 100;
+// keeps a single proposer from filling a round on their own, every proposal also has to lock a bond
+const MAX_PROPOSALS_PER_PROPOSER = 10;
+// a proposal only gets its bond back if it received at least 1 / BOND_REFUND_DIVISOR of the round's votes,
+// so that filling a round with proposals that each get a token of votes costs the bonds of all of them
+const BOND_REFUND_DIVISOR = 20;
+// the number of blocks after a round is executed during which its voters can claim their rewards
+const REWARD_CLAIM_PERIOD = 1000;
 
 storage {
+    // the only address that can initialize the contract, so that nobody can initialize it with a worthless
+    // governance token before the deployer does. the deployer sets it by overriding the storage slot of
+    // this field, sha256("storage_0"), when deploying. nobody can initialize the contract if it is left unset
+    admin: b256 = 0x0000000000000000000000000000000000000000000000000000000000000000,
+
     // tells us if it is initialized yet or not
     state: State = State::NotInitialized,
 
+    // the identity that administers the contract
+    ownership: Ownership = Ownership {
+        owner: Option::None,
+        pending_owner: Option::None,
+    },
+
+    // the settings chosen at initialization
+    config: VotingConfig = VotingConfig {
+        proposal_bond: 0,
+        quorum: 0,
+        tie_policy: TiePolicy::KeepIncumbent,
+        voting_mode: VotingMode::TokenWeighted,
+    },
+
     // contract id of the governance token
     token: ContractId = ContractId {
         value: 0x0000000000000000000000000000000000000000000000000000000000000000,
@@ -35,24 +66,82 @@ storage {
     // the current number of votes for each prospective favorite number
     number_votes: StorageMap<u64, u64> = StorageMap {},
 
+    // the numbers proposed in each round, keyed by (round, index)
+    proposals: StorageMap<(u64, u64), Proposal> = StorageMap {},
+
+    // the number of proposals in each round
+    proposal_counts: StorageMap<u64, u64> = StorageMap {},
+
+    // the number of proposals each user made in each round, keyed by (user, round)
+    proposer_counts: StorageMap<(Identity, u64), u64> = StorageMap {},
+
+    // whether each number has been proposed in each round, keyed by (round, number)
+    proposed: StorageMap<(u64, u64), bool> = StorageMap {},
+
+    // whether each number was knocked out of a round by a runoff, keyed by (round, number)
+    eliminated: StorageMap<(u64, u64), bool> = StorageMap {},
+
+    // whether each round went to a runoff, which is only between the tied proposals
+    runoff_rounds: StorageMap<u64, bool> = StorageMap {},
+
+    // counts every vote cast, used to order votes that happen in the same block
+    vote_sequence: u64 = 0,
+
+    // the vote after which each number last changed its tally, keyed by (round, number)
+    tally_changed_at: StorageMap<(u64, u64), u64> = StorageMap {},
+
     // the balance for each user
     user_balances: StorageMap<Identity, u64> = StorageMap {},
+
+    // the round that is currently being voted on, incremented each time a new favorite number is set
+    round: u64 = 0,
+
+    // the number of votes each user allocated to each number in each round, keyed by (user, round, number)
+    user_votes: StorageMap<(Identity, u64, u64), u64> = StorageMap {},
+
+    // the deposited tokens each user spent on votes in each round that are still locked, keyed by (user, round)
+    locked_balance: StorageMap<(Identity, u64), u64> = StorageMap {},
+
+    // the deposited tokens each user spent on votes that are still locked across every round
+    total_locked_balance: StorageMap<Identity, u64> = StorageMap {},
+
+    // the voting window of each round that has been scheduled
+    round_windows: StorageMap<u64, RoundWindow> = StorageMap {},
+
+    // the votes currently given to the proposals of each round
+    round_votes: StorageMap<u64, u64> = StorageMap {},
+
+    // whether the last execute of each round found too few votes, which lets the owner reschedule it
+    stalled_rounds: StorageMap<u64, bool> = StorageMap {},
+
+    // the result of each finished round
+    round_results: StorageMap<u64, RoundResult> = StorageMap {},
+
+    // the call of the winning proposal of each round that was won by a call proposal, made by carry_out
+    winning_calls: StorageMap<u64, ContractCall> = StorageMap {},
+
+    // the governance tokens set aside to reward the voters who backed the winner of each round
+    reward_pools: StorageMap<u64, u64> = StorageMap {},
+
+    // whether each user has claimed their reward for each round, keyed by (user, round)
+    reward_claims: StorageMap<(Identity, u64), bool> = StorageMap {},
+
+    // the rewards already claimed from the reward pool of each round
+    rewards_paid: StorageMap<u64, u64> = StorageMap {},
 }
 
 impl Voting for Contract {
-    // initialize with the governance token
+    // initialize with the governance token and the voting settings
     #[storage(read, write)]
-    fn initialize(token: ContractId) {
+    fn initialize(token: ContractId, config: VotingConfig) {
         require(storage.state == State::NotInitialized, InitializationError::CannotReinitialize);
+        require(msg_sender().unwrap() == Identity::Address(Address { value: storage.admin }), InitializationError::SenderNotAdmin);
+        require(0 < config.proposal_bond, InitializationError::ProposalBondCannotBeZero);
 
         storage.token = token;
+        storage.config = config;
+        storage.ownership = Ownership::new(msg_sender().unwrap());
         storage.state = State::Initialized;
-
-        let mut i = 0;
-        while i < MAX_NUM {
-            storage.number_votes.insert(i, 0);
-            i += 1;
-        }
     }
 
     // get the amount of governance tokens in this contract
@@ -70,6 +159,13 @@ impl Voting for Contract {
         storage.user_balances.get(user)
     }
 
+    // get the number of tokens any user has deposited in the contract
+    #[storage(read)]
+    fn get_user_balance_of(user: Identity) -> u64 {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.user_balances.get(user)
+    }
+
     // get the current favorite number
     #[storage(read)]
     fn get_favorite_number() -> u64 {
@@ -77,11 +173,10 @@ impl Voting for Contract {
         storage.favorite_number
     }
 
-    // get the current number of votes for a particular number
+    // get the current number of votes for a particular number, 0 if it was not proposed
     #[storage(read)]
     fn get_number_of_votes(number: u64) -> u64 {
         require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
-        require(number < MAX_NUM, UserError::InvalidNumber);
         storage.number_votes.get(number)
     }
 
@@ -94,6 +189,11 @@ impl Voting for Contract {
 
         let user = msg_sender().unwrap();
         storage.user_balances.insert(user, msg_amount() + storage.user_balances.get(user));
+
+        log(DepositEvent {
+            user: user,
+            amount: msg_amount(),
+        });
     }
 
     // withdraw governance tokens
@@ -111,58 +211,604 @@ impl Voting for Contract {
 
         // Transfer the asset back to the user
         transfer(amount, storage.token, user);
+
+        log(WithdrawEvent {
+            user: user,
+            amount: amount,
+        });
+    }
+
+    // propose a new favorite number for the current round, locking the proposal bond from the caller's balance
+    #[storage(read, write)]
+    fn propose(number: u64) {
+        add_proposal(number, Action::SetFavoriteNumber);
+    }
+
+    // propose a call on another contract for the current round, voted on as `number`, locking the proposal
+    // bond from the caller's balance
+    #[storage(read, write)]
+    fn propose_call(number: u64, call: ContractCall) {
+        add_proposal(number, Action::Call(call));
+    }
+
+    // get the number of proposals in the current round
+    #[storage(read)]
+    fn get_proposal_count() -> u64 {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.proposal_counts.get(storage.round)
+    }
+
+    // get a proposal of the current round
+    #[storage(read)]
+    fn get_proposal(index: u64) -> Proposal {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        require(index < storage.proposal_counts.get(storage.round), UserError::InvalidNumber);
+        storage.proposals.get((storage.round, index))
+    }
+
+    // get the settings chosen at initialization
+    #[storage(read)]
+    fn get_config() -> VotingConfig {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.config
     }
 
-    // vote for the new favorite number
+    // vote for one of the proposed favorite numbers, paying for the votes from the caller's balance
     #[storage(read, write)]
     fn vote(voting_for: u64, vote_amount: u64) {
         require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
-        require(voting_for < MAX_NUM, UserError::InvalidNumber);
+        require(storage.proposed.get((storage.round, voting_for)), UserError::InvalidNumber);
+        require(!storage.eliminated.get((storage.round, voting_for)), UserError::ProposalEliminated);
+        require_voting_open();
+
+        add_votes(msg_sender().unwrap(), voting_for, vote_amount);
+    }
+
+    // take back votes the caller gave a number in the current round and refund what they cost to their balance
+    #[storage(read, write)]
+    fn retract_vote(number: u64, amount: u64) {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        require_voting_open();
+
+        remove_votes(msg_sender().unwrap(), number, amount);
+    }
+
+    // move votes the caller gave one number in the current round to another proposed number, in quadratic
+    // mode the difference in what the votes cost is settled with the caller's balance
+    #[storage(read, write)]
+    fn move_vote(from: u64, to: u64, amount: u64) {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        require(storage.proposed.get((storage.round, to)), UserError::InvalidNumber);
+        require(!storage.eliminated.get((storage.round, to)), UserError::ProposalEliminated);
+        require_voting_open();
 
         let user = msg_sender().unwrap();
-        let user_balance = storage.user_balances.get(user);
+        remove_votes(user, from, amount);
+        add_votes(user, to, amount);
+    }
 
-        require(vote_amount <= user_balance, UserError::InsufficientBalance);
+    // get the number of votes a user gave a number in the current round
+    #[storage(read)]
+    fn get_user_votes(user: Identity, number: u64) -> u64 {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.user_votes.get((user, storage.round, number))
+    }
 
-        storage.user_balances.insert(user, user_balance - vote_amount);
-        storage.number_votes.insert(voting_for, storage.number_votes.get(voting_for) + vote_amount);
+    // get the deposited tokens a user has locked in votes in the current round and in finished rounds they
+    // have not unlocked yet. this is a token balance rather than a vote count, in quadratic mode n votes on a
+    // number lock n * n tokens
+    #[storage(read)]
+    fn get_locked_balance(user: Identity) -> u64 {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.total_locked_balance.get(user)
     }
 
-    // execute the votes and sets the favorite number to the number with the most votes
+    // execute the votes and carry out the proposal with the most votes, which either sets the favorite number
+    // or records the proposed call for `carry_out` to make. the call is not made here so that a target that
+    // reverts cannot keep the round from ending
     //
-    // returns true if a new favorite number is set, and returns false if one is not set (e.g. in a tie)
+    // only a scheduled round can be executed, once its voting window has closed. ties for the most votes are
+    // settled by the tie policy chosen at initialization, and the round stays open to be rescheduled if nobody
+    // voted or fewer votes than the quorum were cast
     #[storage(read, write)]
-    fn execute() -> bool {
+    fn execute() -> ExecuteOutcome {
         require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
 
+        let round = storage.round;
+        let window = storage.round_windows.get(round);
+        require(window.is_scheduled(), RoundError::RoundNotScheduled);
+        require(window.end <= height(), RoundError::VotingStillOpen);
+
+        let proposal_count = storage.proposal_counts.get(round);
+
         let mut highest_votes = 0;
-        let mut new_favorite_number = Option::None;
+        let mut total_votes = 0;
+        let mut tied = 0;
+
+        // the tied proposals with the lowest number and with the earliest tally
+        let mut lowest_index = 0;
+        let mut lowest_number = 0;
+        let mut earliest_index = 0;
+        let mut earliest_sequence = 0;
 
         let mut i = 0;
-        while i < MAX_NUM {
-            let number_votes = storage.number_votes.get(i);
-            if number_votes > highest_votes {
+        while i < proposal_count {
+            let number = storage.proposals.get((round, i)).number;
+            let number_votes = storage.number_votes.get(number);
+            let sequence = storage.tally_changed_at.get((round, number));
+            total_votes = total_votes + number_votes;
+
+            if storage.eliminated.get((round, number)) || number_votes == 0 {
+                // only the remaining proposals with votes can win
+            } else if number_votes > highest_votes {
                 highest_votes = number_votes;
-                new_favorite_number = Option::Some(i);
+                tied = 1;
+                lowest_index = i;
+                lowest_number = number;
+                earliest_index = i;
+                earliest_sequence = sequence;
             } else if number_votes == highest_votes {
-                new_favorite_number = Option::None;
+                tied = tied + 1;
+                if number < lowest_number {
+                    lowest_index = i;
+                    lowest_number = number;
+                }
+                if sequence < earliest_sequence {
+                    earliest_index = i;
+                    earliest_sequence = sequence;
+                }
             }
             i += 1;
         }
 
-        match new_favorite_number {
-            Option::Some(new_favorite_number) => {
-                let mut i = 0;
-                while i < MAX_NUM {
-                    storage.number_votes.insert(i, 0);
-                    i += 1;
+        if highest_votes == 0 {
+            storage.stalled_rounds.insert(round, true);
+            return report_execution(round, ExecuteOutcome::NoVotes, total_votes);
+        }
+        if total_votes < storage.config.quorum {
+            storage.stalled_rounds.insert(round, true);
+            return report_execution(round, ExecuteOutcome::BelowQuorum, total_votes);
+        }
+
+        let winner_index = if tied == 1 {
+            Option::Some(lowest_index)
+        } else {
+            match storage.config.tie_policy {
+                TiePolicy::KeepIncumbent => Option::None,
+                TiePolicy::LowestNumber => Option::Some(lowest_index),
+                TiePolicy::FirstToTop => Option::Some(earliest_index),
+                TiePolicy::Runoff => {
+                    start_runoff(round, proposal_count, highest_votes);
+                    return report_execution(round, ExecuteOutcome::RunoffStarted, total_votes);
+                },
+            }
+        };
+
+        close_round(round, proposal_count, total_votes);
+
+        let outcome = match winner_index {
+            Option::Some(index) => {
+                let winner = storage.proposals.get((round, index));
+                match winner.action {
+                    Action::SetFavoriteNumber => {
+                        storage.favorite_number = winner.number;
+                        ExecuteOutcome::Carried(winner.number)
+                    },
+                    Action::Call(call) => {
+                        storage.winning_calls.insert(round, call);
+                        ExecuteOutcome::CallPending(winner.number)
+                    },
                 }
-                storage.favorite_number = new_favorite_number;
-                return true;
             },
-            Option::None => {},
+            Option::None => {
+                // nobody backed a winner, so the reward pool goes to the next round
+                storage.reward_pools.insert(round + 1, storage.reward_pools.get(round + 1) + storage.reward_pools.get(round));
+                storage.reward_pools.insert(round, 0);
+
+                ExecuteOutcome::IncumbentKept
+            },
+        };
+
+        let winning_votes = match winner_index {
+            Option::Some(_) => highest_votes,
+            Option::None => 0,
+        };
+        storage.round_results.insert(round, RoundResult {
+            outcome: outcome,
+            winning_votes: winning_votes,
+            total_votes: total_votes,
+            height: height(),
+        });
+
+        report_execution(round, outcome, total_votes)
+    }
+
+    // make the call of a finished round that was won by a call proposal, anyone can carry it out once. if the
+    // target reverts the call stays pending and can be tried again, the round has ended either way
+    //
+    // returns Carried if the target returned true and CallFailed if it returned false
+    #[storage(read, write)]
+    fn carry_out(round: u64) -> ExecuteOutcome {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        require(round < storage.round, UserError::RoundNotOver);
+
+        let result = storage.round_results.get(round);
+        let pending_call = result.pending_call();
+        require(pending_call.is_some(), RoundError::NoPendingCall);
+        let number = pending_call.unwrap();
+
+        // the call is no longer pending while it is being made, so the target cannot make it a second time
+        storage.round_results.insert(round, RoundResult {
+            outcome: ExecuteOutcome::CallFailed(number),
+            winning_votes: result.winning_votes,
+            total_votes: result.total_votes,
+            height: result.height,
+        });
+
+        let call = storage.winning_calls.get(round);
+        let target = abi(Governed, call.target.value);
+        let outcome = if target.execute_call(call.selector, call.arguments) {
+            ExecuteOutcome::Carried(number)
+        } else {
+            ExecuteOutcome::CallFailed(number)
+        };
+
+        storage.round_results.insert(round, RoundResult {
+            outcome: outcome,
+            winning_votes: result.winning_votes,
+            total_votes: result.total_votes,
+            height: result.height,
+        });
+
+        report_execution(round, outcome, result.total_votes)
+    }
+
+    // get the number of finished rounds, which is also the id of the round that is currently being voted on
+    #[storage(read)]
+    fn get_round_count() -> u64 {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.round
+    }
+
+    // get the result of a finished round
+    #[storage(read)]
+    fn get_round(round: u64) -> RoundResult {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        require(round < storage.round, UserError::RoundNotOver);
+        storage.round_results.get(round)
+    }
+
+    // set the block heights during which the current round accepts votes, only the owner can schedule rounds.
+    // every round has to be scheduled before it can be voted on or executed
+    //
+    // a round can be rescheduled while it has no votes, or after execute found fewer votes than the quorum
+    #[storage(read, write)]
+    fn schedule_round(start: u64, end: u64) {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.ownership.only_owner(msg_sender().unwrap());
+        require(start < end, RoundError::InvalidWindow);
+
+        // once a round has votes its window can only be replaced if execute found too few of them
+        let round = storage.round;
+        let window = storage.round_windows.get(round);
+        require(!window.is_scheduled() || storage.round_votes.get(round) == 0 || storage.stalled_rounds.get(round), RoundError::RoundAlreadyStarted);
+
+        storage.round_windows.insert(round, RoundWindow {
+            start: start,
+            end: end,
+        });
+        storage.stalled_rounds.insert(round, false);
+    }
+
+    // get the voting window of a round, both heights are 0 if the round was never scheduled
+    #[storage(read)]
+    fn get_round_window(round: u64) -> RoundWindow {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.round_windows.get(round)
+    }
+
+    // return the votes the caller spent in a finished round to their balance so that they can be withdrawn
+    //
+    // returns the number of votes that were unlocked
+    #[storage(read, write)]
+    fn unlock_votes(round: u64) -> u64 {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        require(round < storage.round, UserError::RoundNotOver);
+
+        let user = msg_sender().unwrap();
+        let locked = storage.locked_balance.get((user, round));
+
+        require(0 < locked, UserError::NoVotesToUnlock);
+
+        storage.locked_balance.insert((user, round), 0);
+        storage.total_locked_balance.insert(user, storage.total_locked_balance.get(user) - locked);
+        storage.user_balances.insert(user, storage.user_balances.get(user) + locked);
+
+        log(UnlockEvent {
+            user: user,
+            round: round,
+            amount: locked,
+        });
+
+        locked
+    }
+
+    // add the forwarded governance tokens to the reward pool of the round that is currently being voted on
+    #[storage(read, write)]
+    fn fund_rewards() {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        require(storage.token == msg_asset_id(), UserError::IncorrectAssetSent);
+        require(0 < msg_amount(), UserError::AmountCannotBeZero);
+
+        let round = storage.round;
+        storage.reward_pools.insert(round, storage.reward_pools.get(round) + msg_amount());
+
+        log(FundEvent {
+            funder: msg_sender().unwrap(),
+            round: round,
+            amount: msg_amount(),
+        });
+    }
+
+    // get the reward pool of a round
+    #[storage(read)]
+    fn get_reward_pool(round: u64) -> u64 {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        storage.reward_pools.get(round)
+    }
+
+    // add the caller's share of a finished round's reward pool to their balance, the share is
+    // proportional to the votes they gave the winning number and rounded down. rewards can only be claimed
+    // for REWARD_CLAIM_PERIOD blocks after the round was executed
+    //
+    // returns the reward
+    #[storage(read, write)]
+    fn claim_reward(round: u64) -> u64 {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        require(round < storage.round, UserError::RoundNotOver);
+
+        let user = msg_sender().unwrap();
+        require(!storage.reward_claims.get((user, round)), UserError::RewardAlreadyClaimed);
+
+        let result = storage.round_results.get(round);
+        let winner = result.winner();
+        require(winner.is_some(), UserError::NoRewardToClaim);
+        require(height() < result.height + REWARD_CLAIM_PERIOD, UserError::ClaimPeriodOver);
+
+        let user_votes = storage.user_votes.get((user, round, winner.unwrap()));
+        let reward = mul_div(storage.reward_pools.get(round), user_votes, result.winning_votes);
+
+        require(0 < reward, UserError::NoRewardToClaim);
+
+        storage.reward_claims.insert((user, round), true);
+        storage.rewards_paid.insert(round, storage.rewards_paid.get(round) + reward);
+        storage.user_balances.insert(user, storage.user_balances.get(user) + reward);
+
+        log(RewardEvent {
+            user: user,
+            round: round,
+            amount: reward,
+        });
+
+        reward
+    }
+
+    // once the claim period of a finished round is over, move what was not claimed from its reward pool,
+    // including the dust left by rounding the rewards down, to the pool of the round that is currently
+    // being voted on. anyone can sweep a round
+    //
+    // returns the amount that was moved
+    #[storage(read, write)]
+    fn sweep_rewards(round: u64) -> u64 {
+        require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+        require(round < storage.round, UserError::RoundNotOver);
+        require(storage.round_results.get(round).height + REWARD_CLAIM_PERIOD <= height(), UserError::ClaimPeriodNotOver);
+
+        let paid = storage.rewards_paid.get(round);
+        let unclaimed = storage.reward_pools.get(round) - paid;
+        require(0 < unclaimed, UserError::NoRewardToSweep);
+
+        storage.reward_pools.insert(round, paid);
+        storage.reward_pools.insert(storage.round, storage.reward_pools.get(storage.round) + unclaimed);
+
+        log(SweepEvent {
+            round: round,
+            into_round: storage.round,
+            amount: unclaimed,
+        });
+
+        unclaimed
+    }
+
+    // propose a new owner, who has to call `accept_ownership` before the transfer takes effect. ownership
+    // cannot be renounced because only the owner can schedule rounds, without one governance would stop
+    // and the votes and bonds of an unfinished round could never be unlocked
+    #[storage(read, write)]
+    fn transfer_ownership(new_owner: Identity) {
+        storage.ownership = storage.ownership.transfer(msg_sender().unwrap(), new_owner);
+    }
+
+    // become the owner, only the proposed owner can accept
+    #[storage(read, write)]
+    fn accept_ownership() {
+        storage.ownership = storage.ownership.accept(msg_sender().unwrap());
+    }
+
+    // get the current owner
+    #[storage(read)]
+    fn owner() -> Identity {
+        storage.ownership.owner()
+    }
+
+    // get the proposed owner
+    #[storage(read)]
+    fn pending_owner() -> Identity {
+        storage.ownership.pending_owner()
+    }
+}
+
+// add a proposal to the current round, locking the proposal bond from the caller's balance
+#[storage(read, write)]
+fn add_proposal(number: u64, action: Action) {
+    require(storage.state == State::Initialized, InitializationError::ContractNotInitialized);
+
+    let round = storage.round;
+    require(!storage.proposed.get((round, number)), UserError::AlreadyProposed);
+
+    // proposals are accepted before the voting window opens but not after it closes, nor during a runoff
+    let window = storage.round_windows.get(round);
+    require(!window.is_scheduled() || height() < window.end, RoundError::VotingClosed);
+    require(!storage.runoff_rounds.get(round), RoundError::RunoffInProgress);
+
+    let proposal_count = storage.proposal_counts.get(round);
+    require(proposal_count < MAX_PROPOSALS, UserError::TooManyProposals);
+
+    let user = msg_sender().unwrap();
+    let proposer_count = storage.proposer_counts.get((user, round));
+    require(proposer_count < MAX_PROPOSALS_PER_PROPOSER, UserError::TooManyProposalsFromSender);
+
+    let user_balance = storage.user_balances.get(user);
+    let bond = storage.config.proposal_bond;
+
+    require(bond <= user_balance, UserError::InsufficientBalance);
+
+    storage.user_balances.insert(user, user_balance - bond);
+    storage.proposals.insert((round, proposal_count), Proposal {
+        number: number,
+        proposer: user,
+        bond: bond,
+        action: action,
+    });
+    storage.proposal_counts.insert(round, proposal_count + 1);
+    storage.proposer_counts.insert((user, round), proposer_count + 1);
+    storage.proposed.insert((round, number), true);
+
+    log(ProposeEvent {
+        proposer: user,
+        number: number,
+        bond: bond,
+    });
+}
+
+// give votes from a user to a number of the current round, the tokens they cost are taken from the user's
+// balance and stay locked until the round ends
+#[storage(read, write)]
+fn add_votes(user: Identity, number: u64, amount: u64) {
+    let round = storage.round;
+    let user_votes = storage.user_votes.get((user, round, number));
+    let user_balance = storage.user_balances.get(user);
+
+    // in quadratic mode the cost of a vote grows with the votes the user already gave the number
+    let cost = vote_cost(storage.config.voting_mode, user_votes + amount) - vote_cost(storage.config.voting_mode, user_votes);
+
+    require(cost <= user_balance, UserError::InsufficientBalance);
+
+    storage.user_balances.insert(user, user_balance - cost);
+    storage.number_votes.insert(number, storage.number_votes.get(number) + amount);
+    storage.round_votes.insert(round, storage.round_votes.get(round) + amount);
+    storage.user_votes.insert((user, round, number), user_votes + amount);
+    storage.locked_balance.insert((user, round), storage.locked_balance.get((user, round)) + cost);
+    storage.total_locked_balance.insert(user, storage.total_locked_balance.get(user) + cost);
+    record_tally_change(round, number);
+
+    log(VoteEvent {
+        user: user,
+        number: number,
+        amount: amount,
+    });
+}
+
+// take back votes a user gave a number of the current round and refund what they cost, reverts if they
+// gave it fewer votes
+#[storage(read, write)]
+fn remove_votes(user: Identity, number: u64, amount: u64) {
+    let round = storage.round;
+    let user_votes = storage.user_votes.get((user, round, number));
+
+    require(amount <= user_votes, UserError::InsufficientVotes);
+
+    let refund = vote_cost(storage.config.voting_mode, user_votes) - vote_cost(storage.config.voting_mode, user_votes - amount);
+
+    storage.user_balances.insert(user, storage.user_balances.get(user) + refund);
+    storage.number_votes.insert(number, storage.number_votes.get(number) - amount);
+    storage.round_votes.insert(round, storage.round_votes.get(round) - amount);
+    storage.user_votes.insert((user, round, number), user_votes - amount);
+    storage.locked_balance.insert((user, round), storage.locked_balance.get((user, round)) - refund);
+    storage.total_locked_balance.insert(user, storage.total_locked_balance.get(user) - refund);
+    record_tally_change(round, number);
+
+    log(RetractEvent {
+        user: user,
+        number: number,
+        amount: amount,
+    });
+}
+
+#[storage(read, write)]
+fn record_tally_change(round: u64, number: u64) {
+    let sequence = storage.vote_sequence + 1;
+    storage.vote_sequence = sequence;
+    storage.tally_changed_at.insert((round, number), sequence);
+}
+
+// reset the tallies of a round and start the next one, the bonds of the proposals that got at least
+// 1 / BOND_REFUND_DIVISOR of the round's votes are returned and the bonds of the others go to the reward
+// pool of the next round
+#[storage(read, write)]
+fn close_round(round: u64, proposal_count: u64, total_votes: u64) {
+    let mut forfeited_bonds = 0;
+    let mut i = 0;
+    while i < proposal_count {
+        let proposal = storage.proposals.get((round, i));
+        let number_votes = storage.number_votes.get(proposal.number);
+        if number_votes == 0 || number_votes < total_votes / BOND_REFUND_DIVISOR {
+            forfeited_bonds = forfeited_bonds + proposal.bond;
+        } else {
+            storage.user_balances.insert(proposal.proposer, storage.user_balances.get(proposal.proposer) + proposal.bond);
         }
+        storage.number_votes.insert(proposal.number, 0);
+        i += 1;
+    }
+    storage.reward_pools.insert(round + 1, storage.reward_pools.get(round + 1) + forfeited_bonds);
+    storage.round = round + 1;
+}
 
-        return false;
+// eliminate every proposal of the round that is not tied for the most votes, the tied ones keep their
+// tallies and the round gets a new voting window as long as its first one
+#[storage(read, write)]
+fn start_runoff(round: u64, proposal_count: u64, highest_votes: u64) {
+    let mut i = 0;
+    while i < proposal_count {
+        let number = storage.proposals.get((round, i)).number;
+        if storage.number_votes.get(number) < highest_votes {
+            storage.eliminated.insert((round, number), true);
+        }
+        i += 1;
     }
+    storage.runoff_rounds.insert(round, true);
+
+    let window = storage.round_windows.get(round);
+    storage.round_windows.insert(round, RoundWindow {
+        start: height(),
+        end: height() + window.end - window.start,
+    });
+}
+
+// log the outcome of executing a round and return it
+fn report_execution(round: u64, outcome: ExecuteOutcome, total_votes: u64) -> ExecuteOutcome {
+    log(ExecuteEvent {
+        round: round,
+        outcome: outcome,
+        total_votes: total_votes,
+    });
+    outcome
+}
+
+// revert unless the current round is scheduled and accepts votes at this block height
+#[storage(read)]
+fn require_voting_open() {
+    let window = storage.round_windows.get(storage.round);
+    require(window.is_scheduled(), RoundError::RoundNotScheduled);
+    require(window.start <= height(), RoundError::VotingNotOpen);
+    require(height() < window.end, RoundError::VotingClosed);
 }
@@ -2,11 +2,11 @@ library voting_library;
 
 use core::ops::Eq;
 
-use std::contract_id::ContractId;
+use std::{assert::assert, contract_id::ContractId, identity::Identity, option::Option};
 
 abi Voting {
     #[storage(read, write)]
-    fn initialize(token: ContractId);
+    fn initialize(token: ContractId, config: VotingConfig);
 
     #[storage(read)]
     fn get_balance() -> u64;
@@ -14,6 +14,9 @@ abi Voting {
     #[storage(read)]
     fn get_user_balance() -> u64;
 
+    #[storage(read)]
+    fn get_user_balance_of(user: Identity) -> u64;
+
     #[storage(read)]
     fn get_favorite_number() -> u64;
 
@@ -26,11 +29,273 @@ abi Voting {
     #[storage(read, write)]
     fn withdraw(amount: u64);
 
+    #[storage(read, write)]
+    fn propose(number: u64);
+
+    #[storage(read, write)]
+    fn propose_call(number: u64, call: ContractCall);
+
+    #[storage(read)]
+    fn get_proposal_count() -> u64;
+
+    #[storage(read)]
+    fn get_proposal(index: u64) -> Proposal;
+
+    #[storage(read)]
+    fn get_config() -> VotingConfig;
+
     #[storage(read, write)]
     fn vote(voting_for: u64, vote_amount: u64);
 
     #[storage(read, write)]
-    fn execute() -> bool;
+    fn retract_vote(number: u64, amount: u64);
+
+    #[storage(read, write)]
+    fn move_vote(from: u64, to: u64, amount: u64);
+
+    #[storage(read)]
+    fn get_user_votes(user: Identity, number: u64) -> u64;
+
+    #[storage(read)]
+    fn get_locked_balance(user: Identity) -> u64;
+
+    #[storage(read, write)]
+    fn execute() -> ExecuteOutcome;
+
+    #[storage(read, write)]
+    fn carry_out(round: u64) -> ExecuteOutcome;
+
+    #[storage(read)]
+    fn get_round_count() -> u64;
+
+    #[storage(read)]
+    fn get_round(round: u64) -> RoundResult;
+
+    #[storage(read, write)]
+    fn schedule_round(start: u64, end: u64);
+
+    #[storage(read)]
+    fn get_round_window(round: u64) -> RoundWindow;
+
+    #[storage(read, write)]
+    fn unlock_votes(round: u64) -> u64;
+
+    #[storage(read, write)]
+    fn fund_rewards();
+
+    #[storage(read)]
+    fn get_reward_pool(round: u64) -> u64;
+
+    #[storage(read, write)]
+    fn claim_reward(round: u64) -> u64;
+
+    #[storage(read, write)]
+    fn sweep_rewards(round: u64) -> u64;
+
+    #[storage(read, write)]
+    fn transfer_ownership(new_owner: Identity);
+
+    #[storage(read, write)]
+    fn accept_ownership();
+
+    #[storage(read)]
+    fn owner() -> Identity;
+
+    #[storage(read)]
+    fn pending_owner() -> Identity;
+}
+
+// the settings chosen at initialization
+pub struct VotingConfig {
+    // the deposited tokens locked by each proposal, returned once the round ends if the proposal got at least
+    // a twentieth of the round's votes. it cannot be 0, otherwise anyone could fill a round with proposals for free
+    proposal_bond: u64,
+    // the number of votes a round needs in total before a proposal can be carried out
+    quorum: u64,
+    // how execute picks a winner when several proposals are tied for the most votes
+    tie_policy: TiePolicy,
+    // how many deposited tokens votes cost
+    voting_mode: VotingMode,
+}
+
+pub enum VotingMode {
+    // every vote costs one deposited token
+    TokenWeighted: (),
+    // holding n votes on a number costs n² deposited tokens
+    Quadratic: (),
+}
+
+pub enum TiePolicy {
+    // no proposal is carried out and the round ends with the current favorite number unchanged
+    KeepIncumbent: (),
+    // the tied proposal with the lowest number wins
+    LowestNumber: (),
+    // the tied proposal that reached its tally first wins
+    FirstToTop: (),
+    // the round continues with only the tied proposals, which keep their tallies
+    Runoff: (),
+}
+
+// what execute did with the current round
+pub enum ExecuteOutcome {
+    // the proposal with this number won and was carried out, and the round ended
+    Carried: u64,
+    // the call proposal with this number won and the round ended, its call is made by carry_out
+    CallPending: u64,
+    // carry_out made the call of the proposal with this number but its target returned false
+    CallFailed: u64,
+    // a tie under TiePolicy::KeepIncumbent ended the round without a winner
+    IncumbentKept: (),
+    // a tie under TiePolicy::Runoff eliminated every proposal that was not tied for the most votes
+    RunoffStarted: (),
+    // fewer votes than the quorum were cast, the round stays open
+    BelowQuorum: (),
+    // nobody voted for any of the remaining proposals, the round stays open
+    NoVotes: (),
+}
+
+// implemented by contracts that the DAO can call through a winning proposal. only contracts with this
+// `execute_call` can be governed: Sway can only call a contract through an abi known at compile time, so
+// the voting contract cannot make an arbitrary call from a selector and arguments chosen at runtime
+abi Governed {
+    // dispatch on `selector` and decode `arguments` however the target needs, returning false if the call
+    // failed. a revert cannot be caught by the caller, it reverts carry_out and leaves the call pending
+    #[storage(read, write)]
+    fn execute_call(selector: u64, arguments: [u64; 4]) -> bool;
+}
+
+// a call to make on another contract, `arguments` are the encoded arguments of the function picked by `selector`
+pub struct ContractCall {
+    target: ContractId,
+    selector: u64,
+    arguments: [u64; 4],
+}
+
+// what happens when a proposal wins
+pub enum Action {
+    SetFavoriteNumber: (),
+    Call: ContractCall,
+}
+
+// a proposal is voted on by its number, which becomes the favorite number unless the proposal makes a call instead
+pub struct Proposal {
+    number: u64,
+    proposer: Identity,
+    bond: u64,
+    action: Action,
+}
+
+// the block heights during which a round accepts votes, from `start` up to but not including `end`
+pub struct RoundWindow {
+    start: u64,
+    end: u64,
+}
+
+impl RoundWindow {
+    // a round that was never scheduled accepts no votes and cannot be executed
+    pub fn is_scheduled(self) -> bool {
+        self.end != 0
+    }
+}
+
+// the record of a finished round, a round that ended in a tie without a winner has no `winning_votes`
+pub struct RoundResult {
+    // how the round ended, one of Carried, CallPending, CallFailed or IncumbentKept
+    outcome: ExecuteOutcome,
+    winning_votes: u64,
+    // the votes cast for every proposal of the round
+    total_votes: u64,
+    // the block height at which the round was executed
+    height: u64,
+}
+
+impl RoundResult {
+    // the number of the proposal that won the round, none if it ended in a tie under TiePolicy::KeepIncumbent
+    pub fn winner(self) -> Option<u64> {
+        match self.outcome {
+            ExecuteOutcome::Carried(number) => Option::Some(number),
+            ExecuteOutcome::CallPending(number) => Option::Some(number),
+            ExecuteOutcome::CallFailed(number) => Option::Some(number),
+            _ => Option::None,
+        }
+    }
+
+    // the number of the call proposal that won the round if carry_out has not made its call yet
+    pub fn pending_call(self) -> Option<u64> {
+        match self.outcome {
+            ExecuteOutcome::CallPending(number) => Option::Some(number),
+            _ => Option::None,
+        }
+    }
+}
+
+// logged by deposit
+pub struct DepositEvent {
+    user: Identity,
+    amount: u64,
+}
+
+// logged by withdraw
+pub struct WithdrawEvent {
+    user: Identity,
+    amount: u64,
+}
+
+// logged by vote, and by move_vote for the number the votes moved to, `amount` is the number of votes rather
+// than what they cost
+pub struct VoteEvent {
+    user: Identity,
+    number: u64,
+    amount: u64,
+}
+
+// logged by retract_vote, and by move_vote for the number the votes moved from
+pub struct RetractEvent {
+    user: Identity,
+    number: u64,
+    amount: u64,
+}
+
+// logged by execute whatever the outcome and by carry_out, `total_votes` are the votes cast in the round
+pub struct ExecuteEvent {
+    round: u64,
+    outcome: ExecuteOutcome,
+    total_votes: u64,
+}
+
+// logged by propose and propose_call, `bond` is what the proposer locked
+pub struct ProposeEvent {
+    proposer: Identity,
+    number: u64,
+    bond: u64,
+}
+
+// logged by unlock_votes, `amount` is the deposited tokens returned to the user's balance
+pub struct UnlockEvent {
+    user: Identity,
+    round: u64,
+    amount: u64,
+}
+
+// logged by fund_rewards, `round` is the round whose reward pool was funded
+pub struct FundEvent {
+    funder: Identity,
+    round: u64,
+    amount: u64,
+}
+
+// logged by claim_reward
+pub struct RewardEvent {
+    user: Identity,
+    round: u64,
+    amount: u64,
+}
+
+// logged by sweep_rewards, the unclaimed `amount` of the pool of `round` moved to the pool of `into_round`
+pub struct SweepEvent {
+    round: u64,
+    into_round: u64,
+    amount: u64,
 }
 
 pub enum State {
@@ -47,3 +312,67 @@ impl Eq for State {
         }
     }
 }
+
+// (a * b) / c rounded down, computed with a 128 bit intermediate product so that it cannot overflow
+//
+// reverts if c is 0 or if the result does not fit in a u64
+pub fn mul_div(a: u64, b: u64, c: u64) -> u64 {
+    let low_mask = 0xffffffff;
+    let without_high_bit = 0x7fffffffffffffff;
+    let max = 0xffffffffffffffff;
+
+    // split the operands into 32 bit halves so that each partial product fits in a u64
+    let a_high = a >> 32;
+    let a_low = a & low_mask;
+    let b_high = b >> 32;
+    let b_low = b & low_mask;
+
+    let low_low = a_low * b_low;
+    let high_low = a_high * b_low;
+    let low_high = a_low * b_high;
+    let high_high = a_high * b_high;
+
+    let middle = (low_low >> 32) + (high_low & low_mask) + low_high;
+    let high = high_high + (high_low >> 32) + (middle >> 32);
+    let mut low = ((middle & low_mask) << 32) + (low_low & low_mask);
+
+    assert(high < c);
+
+    // long division, shifting the low word into the remainder one bit at a time
+    let mut remainder = high;
+    let mut quotient = 0;
+    let mut i = 0;
+    while i < 64 {
+        let carry = remainder > without_high_bit;
+        remainder = ((remainder & without_high_bit) << 1) + (low >> 63);
+        low = (low & without_high_bit) << 1;
+        quotient = quotient << 1;
+
+        if carry || c <= remainder {
+            // with the carry the real remainder is 2^64 + remainder, which is still less than 2c
+            remainder = if carry {
+                remainder + (max - c) + 1
+            } else {
+                remainder - c
+            };
+            quotient = quotient + 1;
+        }
+        i += 1;
+    }
+
+    quotient
+}
+
+// the deposited tokens it costs a user to hold `votes` votes on a single number
+//
+// in quadratic mode reverts if the cost does not fit in a u64, which is more than could ever be deposited
+pub fn vote_cost(mode: VotingMode, votes: u64) -> u64 {
+    match mode {
+        VotingMode::TokenWeighted => votes,
+        VotingMode::Quadratic => {
+            // (2^32 - 1)^2 is the largest square below 2^64
+            assert(votes <= 0xffffffff);
+            votes * votes
+        },
+    }
+}
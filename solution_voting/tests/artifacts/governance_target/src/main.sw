@@ -0,0 +1,81 @@
+contract;
+
+// a contract for the tests that records every call the voting contract makes to it
+
+use std::{
+    chain::auth::msg_sender,
+    contract_id::ContractId,
+    identity::Identity,
+    result::*,
+    revert::revert,
+};
+
+abi GovernanceTarget {
+    // same signature as `execute_call` in the Governed abi of voting_library.sw, so the voting contract can call it
+    #[storage(read, write)]
+    fn execute_call(selector: u64, arguments: [u64; 4]) -> bool;
+
+    #[storage(read)]
+    fn get_call_count() -> u64;
+
+    #[storage(read)]
+    fn get_last_caller() -> Identity;
+
+    #[storage(read)]
+    fn get_last_selector() -> u64;
+
+    #[storage(read)]
+    fn get_last_arguments() -> [u64; 4];
+}
+
+// calls with this selector fail, so that the tests can check how the voting contract handles a failed call
+const FAILING_SELECTOR = 0;
+// calls with this selector revert, so that the tests can check that a reverting target cannot block a round
+const REVERTING_SELECTOR = 1;
+
+storage {
+    call_count: u64 = 0,
+    last_caller: Identity = Identity::ContractId(ContractId {
+        value: 0x0000000000000000000000000000000000000000000000000000000000000000,
+    }),
+    last_selector: u64 = 0,
+    last_arguments: [u64; 4] = [0, 0, 0, 0],
+}
+
+impl GovernanceTarget for Contract {
+    #[storage(read, write)]
+    fn execute_call(selector: u64, arguments: [u64; 4]) -> bool {
+        if selector == FAILING_SELECTOR {
+            return false;
+        }
+        if selector == REVERTING_SELECTOR {
+            revert(0);
+        }
+
+        storage.call_count = storage.call_count + 1;
+        storage.last_caller = msg_sender().unwrap();
+        storage.last_selector = selector;
+        storage.last_arguments = arguments;
+        true
+    }
+
+    #[storage(read)]
+    fn get_call_count() -> u64 {
+        storage.call_count
+    }
+
+    #[storage(read)]
+    fn get_last_caller() -> Identity {
+        storage.last_caller
+    }
+
+    #[storage(read)]
+    fn get_last_selector() -> u64 {
+        storage.last_selector
+    }
+
+    #[storage(read)]
+    fn get_last_arguments() -> [u64; 4] {
+        storage.last_arguments
+    }
+}
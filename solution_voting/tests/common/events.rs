@@ -0,0 +1,242 @@
+use crate::utils::{ExecuteOutcome, Identity};
+use fuels::tx::{Address, ContractId, Receipt};
+
+// the ABI does not describe logged types, so these mirror the event structs in voting_library.sw and
+// are decoded by hand from the LogData receipts
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct DepositEvent {
+    pub(crate) user: Identity,
+    pub(crate) amount: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct WithdrawEvent {
+    pub(crate) user: Identity,
+    pub(crate) amount: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct VoteEvent {
+    pub(crate) user: Identity,
+    pub(crate) number: u64,
+    pub(crate) amount: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct RetractEvent {
+    pub(crate) user: Identity,
+    pub(crate) number: u64,
+    pub(crate) amount: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct ExecuteEvent {
+    pub(crate) round: u64,
+    pub(crate) outcome: ExecuteOutcome,
+    pub(crate) total_votes: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct ProposeEvent {
+    pub(crate) proposer: Identity,
+    pub(crate) number: u64,
+    pub(crate) bond: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct UnlockEvent {
+    pub(crate) user: Identity,
+    pub(crate) round: u64,
+    pub(crate) amount: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct FundEvent {
+    pub(crate) funder: Identity,
+    pub(crate) round: u64,
+    pub(crate) amount: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct RewardEvent {
+    pub(crate) user: Identity,
+    pub(crate) round: u64,
+    pub(crate) amount: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct SweepEvent {
+    pub(crate) round: u64,
+    pub(crate) into_round: u64,
+    pub(crate) amount: u64,
+}
+
+pub(crate) trait Event: Sized {
+    fn decode(log: &mut LogReader) -> Self;
+}
+
+// reads the fields of a logged struct in order, every field takes up a whole number of 8 byte words
+pub(crate) struct LogReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> LogReader<'a> {
+    fn take(&mut self, length: usize) -> &'a [u8] {
+        assert!(self.data.len() >= length, "log is shorter than the event");
+        let (field, rest) = self.data.split_at(length);
+        self.data = rest;
+        field
+    }
+
+    fn word(&mut self) -> u64 {
+        u64::from_be_bytes(self.take(8).try_into().unwrap())
+    }
+
+    fn bits(&mut self) -> [u8; 32] {
+        self.take(32).try_into().unwrap()
+    }
+
+    // an enum is its variant index followed by the variant's value, padded to the size of the largest one
+    fn identity(&mut self) -> Identity {
+        match (self.word(), self.bits()) {
+            (0, bits) => Identity::Address(Address::from(bits)),
+            (1, bits) => Identity::ContractId(ContractId::from(bits)),
+            (variant, _) => panic!("unknown identity variant: {}", variant),
+        }
+    }
+
+    fn outcome(&mut self) -> ExecuteOutcome {
+        match (self.word(), self.word()) {
+            (0, number) => ExecuteOutcome::Carried(number),
+            (1, number) => ExecuteOutcome::CallPending(number),
+            (2, number) => ExecuteOutcome::CallFailed(number),
+            (3, _) => ExecuteOutcome::IncumbentKept(),
+            (4, _) => ExecuteOutcome::RunoffStarted(),
+            (5, _) => ExecuteOutcome::BelowQuorum(),
+            (6, _) => ExecuteOutcome::NoVotes(),
+            (variant, _) => panic!("unknown outcome variant: {}", variant),
+        }
+    }
+}
+
+impl Event for DepositEvent {
+    fn decode(log: &mut LogReader) -> Self {
+        Self {
+            user: log.identity(),
+            amount: log.word(),
+        }
+    }
+}
+
+impl Event for WithdrawEvent {
+    fn decode(log: &mut LogReader) -> Self {
+        Self {
+            user: log.identity(),
+            amount: log.word(),
+        }
+    }
+}
+
+impl Event for VoteEvent {
+    fn decode(log: &mut LogReader) -> Self {
+        Self {
+            user: log.identity(),
+            number: log.word(),
+            amount: log.word(),
+        }
+    }
+}
+
+impl Event for RetractEvent {
+    fn decode(log: &mut LogReader) -> Self {
+        Self {
+            user: log.identity(),
+            number: log.word(),
+            amount: log.word(),
+        }
+    }
+}
+
+impl Event for ExecuteEvent {
+    fn decode(log: &mut LogReader) -> Self {
+        Self {
+            round: log.word(),
+            outcome: log.outcome(),
+            total_votes: log.word(),
+        }
+    }
+}
+
+impl Event for ProposeEvent {
+    fn decode(log: &mut LogReader) -> Self {
+        Self {
+            proposer: log.identity(),
+            number: log.word(),
+            bond: log.word(),
+        }
+    }
+}
+
+impl Event for UnlockEvent {
+    fn decode(log: &mut LogReader) -> Self {
+        Self {
+            user: log.identity(),
+            round: log.word(),
+            amount: log.word(),
+        }
+    }
+}
+
+impl Event for FundEvent {
+    fn decode(log: &mut LogReader) -> Self {
+        Self {
+            funder: log.identity(),
+            round: log.word(),
+            amount: log.word(),
+        }
+    }
+}
+
+impl Event for RewardEvent {
+    fn decode(log: &mut LogReader) -> Self {
+        Self {
+            user: log.identity(),
+            round: log.word(),
+            amount: log.word(),
+        }
+    }
+}
+
+impl Event for SweepEvent {
+    fn decode(log: &mut LogReader) -> Self {
+        Self {
+            round: log.word(),
+            into_round: log.word(),
+            amount: log.word(),
+        }
+    }
+}
+
+// the data of every struct logged during a call, in the order it was logged
+pub(crate) fn logs(receipts: &[Receipt]) -> Vec<&[u8]> {
+    receipts
+        .iter()
+        .filter_map(|receipt| match receipt {
+            Receipt::LogData { data, .. } => Some(data.as_slice()),
+            _ => None,
+        })
+        .collect()
+}
+
+pub(crate) fn decode_log<T: Event>(data: &[u8]) -> T {
+    let mut log = LogReader { data };
+    let event = T::decode(&mut log);
+    assert!(log.data.is_empty(), "log is longer than the event");
+    event
+}
+
+// decode every struct logged during a call that only logs events of type `T`
+pub(crate) fn decode_logs<T: Event>(receipts: &[Receipt]) -> Vec<T> {
+    logs(receipts).into_iter().map(decode_log).collect()
+}
@@ -1,7 +1,10 @@
 #![allow(dead_code)]
 
+#[path = "common/events.rs"]
+mod events;
 mod utils;
 
+use events::*;
 use fuels::prelude::*;
 use utils::*;
 
@@ -50,7 +53,7 @@ mod should_pass {
         let (token_contract_id, _, [deployer, _, _, _]) = setup_tests().await;
 
         // initialize the voting contract
-        initialize_voting_contract(token_contract_id, &deployer.voting_handle).await;
+        initialize_voting_contract(token_contract_id, &deployer).await;
 
         // check starting favorite number
         assert_eq!(get_favorite_number(&deployer.voting_handle).await, 0);
@@ -62,7 +65,7 @@ mod should_pass {
             setup_tests().await;
 
         // initialize the voting contract
-        initialize_voting_contract(token_contract_id, &deployer.voting_handle).await;
+        initialize_voting_contract(token_contract_id, &deployer).await;
 
         // mint tokens and send them to users
         mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
@@ -89,12 +92,34 @@ mod should_pass {
         );
 
         // expect the contract balance to have tokens
-        assert_eq!(get_contract_balance(&deployer.voting_handle).await, 600_000);
+        assert_eq!(
+            get_contract_balance(&deployer.voting_handle).await,
+            DEPLOYER_DEPOSIT + 600_000
+        );
 
         // expect the users to have tokens in the contract
+        assert_eq!(get_balance_of(&user_1).await, 100_000);
+        assert_eq!(get_balance_of(&user_2).await, 200_000);
+        assert_eq!(get_balance_of(&user_3).await, 300_000);
+    }
+
+    #[tokio::test]
+    async fn anyone_can_read_a_users_balance() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 100_000).await;
+
+        // the deployer reads user_1's balance, which user_1 reads as their own
+        let user = Identity::Address(user_1.wallet.address());
+        assert_eq!(
+            get_user_balance_of(&deployer.voting_handle, user).await,
+            100_000
+        );
         assert_eq!(get_user_balance(&user_1.voting_handle).await, 100_000);
-        assert_eq!(get_user_balance(&user_2.voting_handle).await, 200_000);
-        assert_eq!(get_user_balance(&user_3.voting_handle).await, 300_000);
     }
 
     #[tokio::test]
@@ -103,7 +128,7 @@ mod should_pass {
             setup_tests().await;
 
         // initialize the voting contract
-        initialize_voting_contract(token_contract_id, &deployer.voting_handle).await;
+        initialize_voting_contract(token_contract_id, &deployer).await;
 
         // mint tokens and send them to users
         mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
@@ -135,12 +160,15 @@ mod should_pass {
         );
 
         // expect the contract balance to have tokens
-        assert_eq!(get_contract_balance(&deployer.voting_handle).await, 900_000);
+        assert_eq!(
+            get_contract_balance(&deployer.voting_handle).await,
+            DEPLOYER_DEPOSIT + 900_000
+        );
 
         // expect the users to have tokens in the contract
-        assert_eq!(get_user_balance(&user_1.voting_handle).await, 400_000);
-        assert_eq!(get_user_balance(&user_2.voting_handle).await, 300_000);
-        assert_eq!(get_user_balance(&user_3.voting_handle).await, 200_000);
+        assert_eq!(get_balance_of(&user_1).await, 400_000);
+        assert_eq!(get_balance_of(&user_2).await, 300_000);
+        assert_eq!(get_balance_of(&user_3).await, 200_000);
     }
 
     #[tokio::test]
@@ -148,7 +176,7 @@ mod should_pass {
         let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
 
         // initialize the voting contract
-        initialize_voting_contract(token_contract_id, &deployer.voting_handle).await;
+        initialize_voting_contract(token_contract_id, &deployer).await;
 
         // mint tokens and send them to users
         mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
@@ -156,6 +184,9 @@ mod should_pass {
         // deposit tokens from users
         deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
 
+        // propose numbers to vote on
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+
         // vote for numbers
         vote_for_number(&user_1.voting_handle, 5, 500_000).await;
 
@@ -164,6 +195,9 @@ mod should_pass {
             get_number_of_votes(&deployer.voting_handle, 5).await,
             500_000
         );
+
+        // numbers that were not proposed have no votes
+        assert_eq!(get_number_of_votes(&deployer.voting_handle, 6).await, 0);
     }
 
     #[tokio::test]
@@ -171,7 +205,7 @@ mod should_pass {
         let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
 
         // initialize the voting contract
-        initialize_voting_contract(token_contract_id, &deployer.voting_handle).await;
+        initialize_voting_contract(token_contract_id, &deployer).await;
 
         // mint tokens and send them to users
         mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
@@ -179,6 +213,9 @@ mod should_pass {
         // deposit tokens from users
         deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
 
+        // propose numbers to vote on
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+
         // vote for numbers
         vote_for_number(&user_1.voting_handle, 5, 500_000).await;
 
@@ -189,7 +226,10 @@ mod should_pass {
         );
 
         // execute the voting system and check to see if a new favorite number was set
-        assert!(execute_in_voting_contract(&deployer.voting_handle).await);
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(5)
+        );
 
         // check starting favorite number
         assert_eq!(get_favorite_number(&deployer.voting_handle).await, 5);
@@ -201,7 +241,7 @@ mod should_pass {
             setup_tests().await;
 
         // initialize the voting contract
-        initialize_voting_contract(token_contract_id, &deployer.voting_handle).await;
+        initialize_voting_contract(token_contract_id, &deployer).await;
 
         // mint tokens and send them to users
         mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
@@ -213,6 +253,9 @@ mod should_pass {
         deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 500_000).await;
         deposit_into_voting_contract(&user_3.voting_handle, token_contract_id, 500_000).await;
 
+        // propose numbers to vote on
+        propose_numbers(&deployer.voting_handle, &[5, 55, 99]).await;
+
         // vote for numbers
         vote_for_number(&user_1.voting_handle, 5, 100_000).await;
         vote_for_number(&user_2.voting_handle, 55, 200_000).await;
@@ -239,7 +282,7 @@ mod should_pass {
             setup_tests().await;
 
         // initialize the voting contract
-        initialize_voting_contract(token_contract_id, &deployer.voting_handle).await;
+        initialize_voting_contract(token_contract_id, &deployer).await;
 
         // mint tokens and send them to users
         mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
@@ -251,6 +294,9 @@ mod should_pass {
         deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 500_000).await;
         deposit_into_voting_contract(&user_3.voting_handle, token_contract_id, 500_000).await;
 
+        // propose numbers to vote on
+        propose_numbers(&deployer.voting_handle, &[5, 55, 99]).await;
+
         // vote for numbers
         vote_for_number(&user_1.voting_handle, 5, 100_000).await;
         vote_for_number(&user_2.voting_handle, 55, 200_000).await;
@@ -271,7 +317,10 @@ mod should_pass {
         );
 
         // execute the voting system and check to see if a new favorite number was set
-        assert!(execute_in_voting_contract(&deployer.voting_handle).await);
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(99)
+        );
 
         // check starting favorite number
         assert_eq!(get_favorite_number(&deployer.voting_handle).await, 99);
@@ -283,7 +332,7 @@ mod should_pass {
             setup_tests().await;
 
         // initialize the voting contract
-        initialize_voting_contract(token_contract_id, &deployer.voting_handle).await;
+        initialize_voting_contract(token_contract_id, &deployer).await;
 
         // mint tokens and send them to users
         mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
@@ -295,6 +344,9 @@ mod should_pass {
         deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 500_000).await;
         deposit_into_voting_contract(&user_3.voting_handle, token_contract_id, 500_000).await;
 
+        // propose numbers to vote on
+        propose_numbers(&deployer.voting_handle, &[5, 1, 55, 56, 57, 99, 4]).await;
+
         // vote for numbers
         vote_for_number(&user_1.voting_handle, 5, 100_000).await;
         vote_for_number(&user_1.voting_handle, 1, 400_000).await;
@@ -335,7 +387,7 @@ mod should_pass {
             setup_tests().await;
 
         // initialize the voting contract
-        initialize_voting_contract(token_contract_id, &deployer.voting_handle).await;
+        initialize_voting_contract(token_contract_id, &deployer).await;
 
         // mint tokens and send them to users
         mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
@@ -347,6 +399,9 @@ mod should_pass {
         deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 500_000).await;
         deposit_into_voting_contract(&user_3.voting_handle, token_contract_id, 500_000).await;
 
+        // propose numbers to vote on
+        propose_numbers(&deployer.voting_handle, &[5, 1, 55, 56, 57, 99, 4]).await;
+
         // vote for numbers
         vote_for_number(&user_1.voting_handle, 5, 100_000).await;
         vote_for_number(&user_1.voting_handle, 1, 400_000).await;
@@ -381,9 +436,1402 @@ mod should_pass {
         );
 
         // execute the voting system and check to see if a new favorite number was set
-        assert!(execute_in_voting_contract(&deployer.voting_handle).await);
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(1)
+        );
 
         // check starting favorite number
         assert_eq!(get_favorite_number(&deployer.voting_handle).await, 1);
     }
+
+    #[tokio::test]
+    async fn users_can_retract_votes() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+
+        propose_numbers(&deployer.voting_handle, &[1]).await;
+        vote_for_number(&user_1.voting_handle, 1, 400_000).await;
+
+        // every retraction moves votes back to the balance
+        retract_vote(&user_1.voting_handle, 1, 150_000).await;
+        assert_eq!(
+            get_number_of_votes(&deployer.voting_handle, 1).await,
+            250_000
+        );
+        assert_eq!(get_balance_of(&user_1).await, 250_000);
+
+        retract_vote(&user_1.voting_handle, 1, 250_000).await;
+        assert_eq!(get_number_of_votes(&deployer.voting_handle, 1).await, 0);
+        assert_eq!(get_balance_of(&user_1).await, 500_000);
+
+        // retracted votes can be withdrawn straight away
+        withdraw_from_voting_contract(&user_1.voting_handle, 500_000).await;
+        assert_eq!(get_balance_of(&user_1).await, 0);
+    }
+
+    #[tokio::test]
+    async fn users_can_move_votes() {
+        let (token_contract_id, token_handle, [deployer, user_1, user_2, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_2.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 300_000).await;
+
+        propose_numbers(&deployer.voting_handle, &[1, 2]).await;
+        vote_for_number(&user_1.voting_handle, 1, 400_000).await;
+        vote_for_number(&user_2.voting_handle, 2, 300_000).await;
+
+        // moving votes changes the tallies but not the balance
+        move_vote(&user_1.voting_handle, 1, 2, 150_000).await;
+        assert_eq!(
+            get_number_of_votes(&deployer.voting_handle, 1).await,
+            250_000
+        );
+        assert_eq!(
+            get_number_of_votes(&deployer.voting_handle, 2).await,
+            450_000
+        );
+        assert_eq!(get_balance_of(&user_1).await, 100_000);
+
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(2)
+        );
+
+        // the moved votes are unlocked with the rest
+        assert_eq!(unlock_votes(&user_1.voting_handle, 0).await, 400_000);
+    }
+
+    #[tokio::test]
+    async fn anyone_can_read_a_users_votes() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 1_000_000).await;
+
+        propose_numbers(&deployer.voting_handle, &[1, 2]).await;
+        vote_for_number(&user_1.voting_handle, 1, 400_000).await;
+        vote_for_number(&user_1.voting_handle, 2, 100_000).await;
+
+        // the deployer reads user_1's votes
+        let user = Identity::Address(user_1.wallet.address());
+        assert_eq!(
+            get_user_votes(&deployer.voting_handle, user.clone(), 1).await,
+            400_000
+        );
+        assert_eq!(
+            get_user_votes(&deployer.voting_handle, user.clone(), 2).await,
+            100_000
+        );
+        assert_eq!(
+            get_locked_balance(&deployer.voting_handle, user.clone()).await,
+            500_000
+        );
+
+        // the votes stay locked after the round ends until they are unlocked
+        execute_in_voting_contract(&deployer).await;
+        assert_eq!(
+            get_user_votes(&deployer.voting_handle, user.clone(), 1).await,
+            0
+        );
+        assert_eq!(
+            get_locked_balance(&deployer.voting_handle, user.clone()).await,
+            500_000
+        );
+
+        unlock_votes(&user_1.voting_handle, 0).await;
+        assert_eq!(get_locked_balance(&deployer.voting_handle, user).await, 0);
+    }
+
+    #[tokio::test]
+    async fn mutating_calls_log_events() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        let user = Identity::Address(user_1.wallet.address());
+
+        let receipts =
+            deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        assert_eq!(
+            decode_logs::<DepositEvent>(&receipts),
+            vec![DepositEvent {
+                user: user.clone(),
+                amount: 500_000
+            }]
+        );
+
+        let owner = Identity::Address(deployer.wallet.address());
+        let receipts = propose_number(&deployer.voting_handle, 1).await;
+        assert_eq!(
+            decode_logs::<ProposeEvent>(&receipts),
+            vec![ProposeEvent {
+                proposer: owner.clone(),
+                number: 1,
+                bond: PROPOSAL_BOND
+            }]
+        );
+        propose_number(&deployer.voting_handle, 2).await;
+
+        mint_and_send_to_address(&token_handle, 1_000, deployer.wallet.address()).await;
+        let receipts = fund_rewards(&deployer.voting_handle, token_contract_id, 1_000).await;
+        assert_eq!(
+            decode_logs::<FundEvent>(&receipts),
+            vec![FundEvent {
+                funder: owner,
+                round: 0,
+                amount: 1_000
+            }]
+        );
+
+        let receipts = vote_for_number(&user_1.voting_handle, 1, 300_000).await;
+        assert_eq!(
+            decode_logs::<VoteEvent>(&receipts),
+            vec![VoteEvent {
+                user: user.clone(),
+                number: 1,
+                amount: 300_000
+            }]
+        );
+
+        let receipts = retract_vote(&user_1.voting_handle, 1, 100_000).await;
+        assert_eq!(
+            decode_logs::<RetractEvent>(&receipts),
+            vec![RetractEvent {
+                user: user.clone(),
+                number: 1,
+                amount: 100_000
+            }]
+        );
+
+        // moving votes logs a retraction from one number and a vote for the other
+        let receipts = move_vote(&user_1.voting_handle, 1, 2, 50_000).await;
+        let logged = logs(&receipts);
+        assert_eq!(logged.len(), 2);
+        assert_eq!(
+            decode_log::<RetractEvent>(logged[0]),
+            RetractEvent {
+                user: user.clone(),
+                number: 1,
+                amount: 50_000
+            }
+        );
+        assert_eq!(
+            decode_log::<VoteEvent>(logged[1]),
+            VoteEvent {
+                user: user.clone(),
+                number: 2,
+                amount: 50_000
+            }
+        );
+
+        close_window(&deployer).await;
+        let receipts = deployer
+            .voting_handle
+            .execute()
+            .call()
+            .await
+            .unwrap()
+            .receipts;
+        assert_eq!(
+            decode_logs::<ExecuteEvent>(&receipts),
+            vec![ExecuteEvent {
+                round: 0,
+                outcome: ExecuteOutcome::Carried(1),
+                total_votes: 200_000
+            }]
+        );
+
+        // user_1 backed the winner on their own, so they get the whole reward pool
+        let receipts = user_1
+            .voting_handle
+            .claim_reward(0)
+            .call()
+            .await
+            .unwrap()
+            .receipts;
+        assert_eq!(
+            decode_logs::<RewardEvent>(&receipts),
+            vec![RewardEvent {
+                user: user.clone(),
+                round: 0,
+                amount: 1_000
+            }]
+        );
+
+        let receipts = user_1
+            .voting_handle
+            .unlock_votes(0)
+            .call()
+            .await
+            .unwrap()
+            .receipts;
+        assert_eq!(
+            decode_logs::<UnlockEvent>(&receipts),
+            vec![UnlockEvent {
+                user: user.clone(),
+                round: 0,
+                amount: 200_000
+            }]
+        );
+
+        let receipts = withdraw_from_voting_contract(&user_1.voting_handle, 300_000).await;
+        assert_eq!(
+            decode_logs::<WithdrawEvent>(&receipts),
+            vec![WithdrawEvent {
+                user,
+                amount: 300_000
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn quadratic_votes_cost_their_square() {
+        let (token_contract_id, token_handle, [deployer, user_1, user_2, _]) = setup_tests().await;
+
+        // initialize the voting contract in quadratic mode
+        initialize_voting_contract_with_config(
+            token_contract_id,
+            &deployer,
+            VotingConfig {
+                voting_mode: VotingMode::Quadratic(),
+                ..default_config()
+            },
+        )
+        .await;
+
+        let users = [&user_1, &user_2];
+        for user in users {
+            mint_and_send_to_address(&token_handle, 1_000_000, user.wallet.address()).await;
+            deposit_into_voting_contract(&user.voting_handle, token_contract_id, 1_000_000).await;
+        }
+
+        propose_numbers(&deployer.voting_handle, &[1, 2]).await;
+
+        // topping up votes on a number costs the difference between the squares
+        let mut reference = QuadraticRound::default();
+        for (user, number, votes) in [(0, 1, 300), (0, 1, 200), (0, 2, 100), (1, 2, 900)] {
+            vote_for_number(&users[user].voting_handle, number, votes).await;
+            reference.vote(user, number, votes);
+        }
+        retract_vote(&user_1.voting_handle, 1, 100).await;
+        reference.retract(0, 1, 100);
+        move_vote(&user_2.voting_handle, 2, 1, 300).await;
+        reference.retract(1, 2, 300);
+        reference.vote(1, 1, 300);
+
+        for number in [1, 2] {
+            assert_eq!(
+                get_number_of_votes(&deployer.voting_handle, number).await,
+                reference.tallies[&number]
+            );
+        }
+        for (index, user) in users.iter().enumerate() {
+            assert_eq!(
+                get_balance_of(user).await,
+                1_000_000 - reference.spent[&index]
+            );
+        }
+
+        // user_2's 600 votes on 2 and 300 on 1 cost 360_000 + 90_000
+        assert_eq!(reference.spent[&1], 450_000);
+
+        // both numbers end with 700 votes, and unlocking refunds what the votes cost
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::IncumbentKept()
+        );
+        assert_eq!(
+            unlock_votes(&user_1.voting_handle, 0).await,
+            reference.spent[&0]
+        );
+        assert_eq!(get_balance_of(&user_1).await, 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn users_can_propose_any_number() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+
+        // propose numbers well outside the old 0..100 range
+        propose_numbers(&user_1.voting_handle, &[1_000_000_007, u64::MAX]).await;
+        assert_eq!(get_proposal_count(&deployer.voting_handle).await, 2);
+
+        vote_for_number(&user_1.voting_handle, u64::MAX, 400_000).await;
+
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(u64::MAX)
+        );
+        assert_eq!(get_favorite_number(&deployer.voting_handle).await, u64::MAX);
+
+        // a new round starts without proposals
+        assert_eq!(get_proposal_count(&deployer.voting_handle).await, 0);
+    }
+
+    #[tokio::test]
+    async fn proposal_bonds_are_returned_or_forfeited() {
+        let (token_contract_id, token_handle, [deployer, user_1, user_2, _]) = setup_tests().await;
+
+        // initialize the voting contract with a proposal bond
+        initialize_voting_contract_with_config(
+            token_contract_id,
+            &deployer,
+            VotingConfig {
+                proposal_bond: 10_000,
+                ..default_config()
+            },
+        )
+        .await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_2.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 100_000).await;
+        deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 100_000).await;
+
+        // proposing locks the bond
+        propose_number(&user_1.voting_handle, 7).await;
+        propose_number(&user_2.voting_handle, 8).await;
+        propose_number(&user_2.voting_handle, 9).await;
+        assert_eq!(get_balance_of(&user_1).await, 90_000);
+        assert_eq!(get_balance_of(&user_2).await, 80_000);
+
+        // 8 gets no votes and 9 gets less than a twentieth of the round's votes
+        vote_for_number(&user_2.voting_handle, 7, 50_000).await;
+        vote_for_number(&user_2.voting_handle, 9, 1).await;
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(7)
+        );
+
+        // user_1 gets their bond back, both of user_2's bonds fund the next round's rewards
+        assert_eq!(get_balance_of(&user_1).await, 100_000);
+        assert_eq!(get_balance_of(&user_2).await, 29_999);
+        assert_eq!(get_reward_pool(&deployer.voting_handle, 1).await, 20_000);
+    }
+
+    #[tokio::test]
+    async fn execute_waits_for_the_quorum() {
+        let (token_contract_id, token_handle, [deployer, user_1, user_2, _]) = setup_tests().await;
+
+        // initialize the voting contract with a quorum
+        initialize_voting_contract_with_config(
+            token_contract_id,
+            &deployer,
+            VotingConfig {
+                quorum: 500_000,
+                ..default_config()
+            },
+        )
+        .await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_2.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 400_000).await;
+        deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 100_000).await;
+
+        propose_numbers(&deployer.voting_handle, &[1, 2]).await;
+
+        // below the quorum nothing changes and the round stays open
+        vote_for_number(&user_1.voting_handle, 1, 400_000).await;
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::BelowQuorum()
+        );
+        assert_eq!(get_favorite_number(&deployer.voting_handle).await, 0);
+        assert_eq!(get_round_count(&deployer.voting_handle).await, 0);
+
+        // votes for losing numbers count towards the quorum too
+        vote_for_number(&user_2.voting_handle, 2, 100_000).await;
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(1)
+        );
+        assert_eq!(get_favorite_number(&deployer.voting_handle).await, 1);
+    }
+
+    #[tokio::test]
+    async fn execute_reports_a_round_without_votes() {
+        let (token_contract_id, _, [deployer, _, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        propose_numbers(&deployer.voting_handle, &[1, 2]).await;
+
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::NoVotes()
+        );
+        assert_eq!(get_round_count(&deployer.voting_handle).await, 0);
+    }
+
+    #[tokio::test]
+    async fn tie_keeps_the_incumbent() {
+        let (_, [deployer, _, _, _]) = setup_tied_round(TiePolicy::KeepIncumbent()).await;
+
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::IncumbentKept()
+        );
+        assert_eq!(get_favorite_number(&deployer.voting_handle).await, 0);
+        assert_eq!(get_round_count(&deployer.voting_handle).await, 1);
+    }
+
+    #[tokio::test]
+    async fn tie_goes_to_the_lowest_number() {
+        let (_, [deployer, _, _, _]) = setup_tied_round(TiePolicy::LowestNumber()).await;
+
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(3)
+        );
+        assert_eq!(get_favorite_number(&deployer.voting_handle).await, 3);
+    }
+
+    #[tokio::test]
+    async fn tie_goes_to_the_first_number_to_reach_the_top_tally() {
+        let (_, [deployer, _, _, _]) = setup_tied_round(TiePolicy::FirstToTop()).await;
+
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(5)
+        );
+        assert_eq!(get_favorite_number(&deployer.voting_handle).await, 5);
+    }
+
+    #[tokio::test]
+    async fn tie_starts_a_runoff() {
+        let (_, [deployer, _, _, user_3]) = setup_tied_round(TiePolicy::Runoff()).await;
+
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::RunoffStarted()
+        );
+
+        // the tied numbers keep their tallies in the same round
+        assert_eq!(get_round_count(&deployer.voting_handle).await, 0);
+        assert_eq!(
+            get_number_of_votes(&deployer.voting_handle, 3).await,
+            200_000
+        );
+        assert_eq!(
+            get_number_of_votes(&deployer.voting_handle, 5).await,
+            200_000
+        );
+
+        vote_for_number(&user_3.voting_handle, 3, 50_000).await;
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(3)
+        );
+        assert_eq!(get_favorite_number(&deployer.voting_handle).await, 3);
+    }
+
+    #[tokio::test]
+    async fn failed_winning_call_still_ends_the_round() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+        let (target_id, target_handle) = deploy_governance_target(&deployer.wallet).await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+
+        // the target fails every call with selector 0
+        propose_call(
+            &user_1.voting_handle,
+            1,
+            ContractCall {
+                target: target_id,
+                selector: 0,
+                arguments: [0, 0, 0, 0],
+            },
+        )
+        .await;
+        vote_for_number(&user_1.voting_handle, 1, 400_000).await;
+
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::CallPending(1)
+        );
+        assert_eq!(
+            carry_out(&deployer.voting_handle, 0, target_id).await,
+            ExecuteOutcome::CallFailed(1)
+        );
+
+        // the round is over even though the call did nothing
+        assert_eq!(get_round_count(&deployer.voting_handle).await, 1);
+        assert_eq!(
+            get_round(&deployer.voting_handle, 0).await.outcome,
+            ExecuteOutcome::CallFailed(1)
+        );
+        assert_eq!(
+            target_handle.get_call_count().call().await.unwrap().value,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn reverting_winning_call_still_ends_the_round() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+        let (target_id, _) = deploy_governance_target(&deployer.wallet).await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+
+        // the target reverts every call with selector 1
+        propose_call(
+            &user_1.voting_handle,
+            1,
+            ContractCall {
+                target: target_id,
+                selector: 1,
+                arguments: [0, 0, 0, 0],
+            },
+        )
+        .await;
+        vote_for_number(&user_1.voting_handle, 1, 400_000).await;
+
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::CallPending(1)
+        );
+
+        // the revert only reverts carry_out, the call stays pending
+        let result = deployer
+            .voting_handle
+            .carry_out(0)
+            .set_contracts(&[target_id])
+            .call()
+            .await;
+        assert!(result.is_err());
+        assert_eq!(
+            get_round(&deployer.voting_handle, 0).await.outcome,
+            ExecuteOutcome::CallPending(1)
+        );
+
+        // the round ended anyway, so the votes can be unlocked
+        assert_eq!(get_round_count(&deployer.voting_handle).await, 1);
+        assert_eq!(unlock_votes(&user_1.voting_handle, 0).await, 400_000);
+    }
+
+    #[tokio::test]
+    async fn winning_call_proposal_calls_the_target() {
+        let (token_contract_id, token_handle, [deployer, user_1, user_2, _]) = setup_tests().await;
+        let (target_id, target_handle) = deploy_governance_target(&deployer.wallet).await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_2.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 500_000).await;
+
+        // one proposal calls the target, the other would set a favorite number
+        propose_call(
+            &user_1.voting_handle,
+            1,
+            ContractCall {
+                target: target_id,
+                selector: 42,
+                arguments: [1, 2, 3, 4],
+            },
+        )
+        .await;
+        propose_number(&user_2.voting_handle, 2).await;
+
+        vote_for_number(&user_1.voting_handle, 1, 300_000).await;
+        vote_for_number(&user_2.voting_handle, 2, 200_000).await;
+
+        // execute ends the round and leaves the call to carry_out
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::CallPending(1)
+        );
+        assert_eq!(
+            target_handle.get_call_count().call().await.unwrap().value,
+            0
+        );
+        assert_eq!(
+            carry_out(&user_2.voting_handle, 0, target_id).await,
+            ExecuteOutcome::Carried(1)
+        );
+        assert_eq!(
+            get_round(&deployer.voting_handle, 0).await.outcome,
+            ExecuteOutcome::Carried(1)
+        );
+
+        // the target received the proposed call from the voting contract
+        assert_eq!(
+            target_handle.get_call_count().call().await.unwrap().value,
+            1
+        );
+        assert_eq!(
+            target_handle
+                .get_last_selector()
+                .call()
+                .await
+                .unwrap()
+                .value,
+            42
+        );
+        assert_eq!(
+            target_handle
+                .get_last_arguments()
+                .call()
+                .await
+                .unwrap()
+                .value,
+            [1, 2, 3, 4]
+        );
+        assert!(matches!(
+            target_handle.get_last_caller().call().await.unwrap().value,
+            Identity::ContractId(_)
+        ));
+
+        // a call proposal does not change the favorite number
+        assert_eq!(get_favorite_number(&deployer.voting_handle).await, 0);
+    }
+
+    #[tokio::test]
+    async fn scheduled_round_only_accepts_votes_in_its_window() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+
+        let now = get_block_height(&deployer.wallet).await;
+        let (start, end) = (now + 10, now + 20);
+        schedule_round(&deployer.voting_handle, start, end).await;
+
+        // proposals are accepted before the window opens
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+
+        advance_to_height(&deployer.wallet, start).await;
+        vote_for_number(&user_1.voting_handle, 5, 500_000).await;
+
+        advance_to_height(&deployer.wallet, end).await;
+        let outcome = deployer.voting_handle.execute().call().await.unwrap().value;
+        assert_eq!(outcome, ExecuteOutcome::Carried(5));
+        assert_eq!(get_favorite_number(&deployer.voting_handle).await, 5);
+
+        // the next round is not scheduled until the owner schedules it
+        let window = get_round_window(&deployer.voting_handle, 1).await;
+        assert_eq!((window.start, window.end), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn every_executed_round_is_recorded() {
+        let (token_contract_id, token_handle, [deployer, user_1, user_2, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_2.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 1_000_000).await;
+        deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 1_000_000).await;
+
+        assert_eq!(get_round_count(&deployer.voting_handle).await, 0);
+
+        // round 0 picks 5
+        propose_numbers(&deployer.voting_handle, &[5, 6]).await;
+        vote_for_number(&user_1.voting_handle, 5, 300_000).await;
+        vote_for_number(&user_2.voting_handle, 6, 100_000).await;
+        close_window(&deployer).await;
+        deployer.voting_handle.execute().call().await.unwrap();
+        let first_height = get_block_height(&deployer.wallet).await;
+        open_round(&deployer).await;
+
+        // round 1 ties and keeps 5
+        propose_numbers(&deployer.voting_handle, &[7, 8]).await;
+        vote_for_number(&user_1.voting_handle, 7, 200_000).await;
+        vote_for_number(&user_2.voting_handle, 8, 200_000).await;
+        close_window(&deployer).await;
+        deployer.voting_handle.execute().call().await.unwrap();
+        let second_height = get_block_height(&deployer.wallet).await;
+
+        assert_eq!(get_round_count(&deployer.voting_handle).await, 2);
+
+        let first = get_round(&deployer.voting_handle, 0).await;
+        assert_eq!(first.outcome, ExecuteOutcome::Carried(5));
+        assert_eq!(first.winning_votes, 300_000);
+        assert_eq!(first.total_votes, 400_000);
+        assert_eq!(first.height, first_height);
+
+        let second = get_round(&deployer.voting_handle, 1).await;
+        assert_eq!(second.outcome, ExecuteOutcome::IncumbentKept());
+        assert_eq!(second.winning_votes, 0);
+        assert_eq!(second.total_votes, 400_000);
+        assert_eq!(second.height, second_height);
+    }
+
+    #[tokio::test]
+    async fn users_can_unlock_votes_and_withdraw() {
+        let (token_contract_id, token_handle, [deployer, user_1, user_2, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        // mint tokens and send them to users
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_2.wallet.address()).await;
+
+        // deposit tokens from users
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 500_000).await;
+
+        // propose numbers to vote on
+        propose_numbers(&deployer.voting_handle, &[5, 6, 7]).await;
+
+        // vote for numbers
+        vote_for_number(&user_1.voting_handle, 5, 300_000).await;
+        vote_for_number(&user_1.voting_handle, 6, 100_000).await;
+        vote_for_number(&user_2.voting_handle, 7, 200_000).await;
+
+        // execute the voting system, which ends round 0
+        assert_eq!(get_round_count(&deployer.voting_handle).await, 0);
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(5)
+        );
+        assert_eq!(get_round_count(&deployer.voting_handle).await, 1);
+
+        // unlock the votes from round 0
+        assert_eq!(unlock_votes(&user_1.voting_handle, 0).await, 400_000);
+        assert_eq!(unlock_votes(&user_2.voting_handle, 0).await, 200_000);
+
+        // expect the users to have their whole deposit back in the contract
+        assert_eq!(get_balance_of(&user_1).await, 500_000);
+        assert_eq!(get_balance_of(&user_2).await, 500_000);
+
+        // withdraw everything
+        withdraw_from_voting_contract(&user_1.voting_handle, 500_000).await;
+        withdraw_from_voting_contract(&user_2.voting_handle, 500_000).await;
+
+        // expect users to have all of their tokens back
+        assert_eq!(
+            get_token_balance_in_wallet(token_contract_id, user_1.wallet).await,
+            Some(1_000_000)
+        );
+        assert_eq!(
+            get_token_balance_in_wallet(token_contract_id, user_2.wallet).await,
+            Some(1_000_000)
+        );
+        assert_eq!(
+            get_contract_balance(&deployer.voting_handle).await,
+            DEPLOYER_DEPOSIT
+        );
+    }
+
+    #[tokio::test]
+    async fn winning_voters_split_the_reward_pool() {
+        let (token_contract_id, token_handle, [deployer, user_1, user_2, user_3]) =
+            setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        // mint tokens and send them to users
+        mint_and_send_to_address(&token_handle, 1_000_000, deployer.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_2.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_3.wallet.address()).await;
+
+        // the deployer funds the reward pool of round 0
+        fund_rewards(&deployer.voting_handle, token_contract_id, 1_000_000).await;
+        assert_eq!(get_reward_pool(&user_1.voting_handle, 0).await, 1_000_000);
+
+        // deposit tokens from users
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 500_000).await;
+        deposit_into_voting_contract(&user_3.voting_handle, token_contract_id, 500_000).await;
+
+        // propose numbers to vote on
+        propose_numbers(&deployer.voting_handle, &[5, 6]).await;
+
+        // user_1 and user_2 back the winner, user_3 does not
+        vote_for_number(&user_1.voting_handle, 5, 100_000).await;
+        vote_for_number(&user_2.voting_handle, 5, 200_000).await;
+        vote_for_number(&user_3.voting_handle, 6, 250_000).await;
+
+        assert_eq!(
+            execute_in_voting_contract(&deployer).await,
+            ExecuteOutcome::Carried(5)
+        );
+
+        // the rewards are pro rata by vote weight and rounded down
+        let reward_1 = claim_reward(&user_1.voting_handle, 0).await;
+        let reward_2 = claim_reward(&user_2.voting_handle, 0).await;
+        assert_eq!(reward_1, expected_reward(1_000_000, 100_000, 300_000));
+        assert_eq!(reward_2, expected_reward(1_000_000, 200_000, 300_000));
+        assert_eq!(reward_1, 333_333);
+        assert_eq!(reward_2, 666_666);
+
+        // the rewards are added to the users' balances
+        assert_eq!(get_balance_of(&user_1).await, 400_000 + 333_333);
+        assert_eq!(get_balance_of(&user_2).await, 300_000 + 666_666);
+
+        // the rounding dust is moved to the reward pool of round 1 once the claim period is over
+        let dust = 1_000_000 - reward_1 - reward_2;
+        assert_eq!(dust, 1);
+        close_claim_period(&deployer, 0).await;
+        assert_eq!(sweep_rewards(&user_3.voting_handle, 0).await, dust);
+        assert_eq!(get_reward_pool(&deployer.voting_handle, 0).await, 999_999);
+        assert_eq!(get_reward_pool(&deployer.voting_handle, 1).await, dust);
+        assert_eq!(
+            get_contract_balance(&deployer.voting_handle).await,
+            DEPLOYER_DEPOSIT + 1_000_000 + 1_500_000
+        );
+    }
+
+    #[tokio::test]
+    async fn unclaimed_rewards_are_swept_into_the_current_round() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, deployer.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+
+        fund_rewards(&deployer.voting_handle, token_contract_id, 1_000_000).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+        vote_for_number(&user_1.voting_handle, 5, 200_000).await;
+        execute_in_voting_contract(&deployer).await;
+
+        // user_1 never claims, so the whole pool goes to round 1
+        close_claim_period(&deployer, 0).await;
+        let receipts = deployer
+            .voting_handle
+            .sweep_rewards(0)
+            .call()
+            .await
+            .unwrap()
+            .receipts;
+        assert_eq!(
+            decode_logs::<SweepEvent>(&receipts),
+            vec![SweepEvent {
+                round: 0,
+                into_round: 1,
+                amount: 1_000_000
+            }]
+        );
+        assert_eq!(get_reward_pool(&deployer.voting_handle, 0).await, 0);
+        assert_eq!(get_reward_pool(&deployer.voting_handle, 1).await, 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn ownership_is_transferred_in_two_steps() {
+        let (token_contract_id, _, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        // the initializer is the first owner
+        assert_eq!(
+            get_owner(&user_1.voting_handle).await,
+            Identity::Address(deployer.wallet.address())
+        );
+
+        // propose user_1 and have them accept
+        transfer_ownership(
+            &deployer.voting_handle,
+            Identity::Address(user_1.wallet.address()),
+        )
+        .await;
+        accept_ownership(&user_1.voting_handle).await;
+
+        assert_eq!(
+            get_owner(&deployer.voting_handle).await,
+            Identity::Address(user_1.wallet.address())
+        );
+    }
+}
+
+#[cfg(test)]
+mod revert {
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_carrying_out_a_round_without_a_pending_call() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+        let (target_id, _) = deploy_governance_target(&deployer.wallet).await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+        vote_for_number(&user_1.voting_handle, 5, 400_000).await;
+        execute_in_voting_contract(&deployer).await;
+
+        // round 0 set a favorite number, there is no call to make
+        carry_out(&deployer.voting_handle, 0, target_id).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_non_admin_initializes() {
+        let (token_contract_id, _, [_, user_1, _, _]) = setup_tests().await;
+
+        // user_1 tries to initialize the voting contract before the deployer does
+        initialize_voting_contract(token_contract_id, &user_1).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_voting_for_a_number_that_was_not_proposed() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+        vote_for_number(&user_1.voting_handle, 6, 500_000).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_voting_before_the_window_opens() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+
+        let now = get_block_height(&deployer.wallet).await;
+        schedule_round(&deployer.voting_handle, now + 100, now + 200).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+
+        vote_for_number(&user_1.voting_handle, 5, 500_000).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_voting_after_the_window_closes() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+
+        let now = get_block_height(&deployer.wallet).await;
+        schedule_round(&deployer.voting_handle, now + 5, now + 10).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+
+        advance_to_height(&deployer.wallet, now + 10).await;
+        vote_for_number(&user_1.voting_handle, 5, 500_000).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_executing_before_the_window_closes() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+
+        let now = get_block_height(&deployer.wallet).await;
+        schedule_round(&deployer.voting_handle, now, now + 100).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+        vote_for_number(&user_1.voting_handle, 5, 500_000).await;
+
+        deployer.voting_handle.execute().call().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_executing_a_round_that_was_not_scheduled() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+        vote_for_number(&user_1.voting_handle, 5, 500_000).await;
+
+        // round 1 is never scheduled
+        close_window(&deployer).await;
+        deployer.voting_handle.execute().call().await.unwrap();
+        deployer.voting_handle.execute().call().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_rescheduling_a_round_with_votes() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+        vote_for_number(&user_1.voting_handle, 5, 500_000).await;
+
+        // the round has votes and did not stall, so the owner cannot push its window back before executing it
+        close_window(&deployer).await;
+        open_round(&deployer).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_non_owner_schedules_a_round() {
+        let (token_contract_id, _, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        schedule_round(&user_1.voting_handle, 0, 100).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_voting_for_a_number_eliminated_by_a_runoff() {
+        let (_, [deployer, _, _, user_3]) = setup_tied_round(TiePolicy::Runoff()).await;
+
+        execute_in_voting_contract(&deployer).await;
+
+        vote_for_number(&user_3.voting_handle, 8, 50_000).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_proposing_during_a_runoff() {
+        let (_, [deployer, _, _, _]) = setup_tied_round(TiePolicy::Runoff()).await;
+
+        execute_in_voting_contract(&deployer).await;
+
+        // the runoff is only between 5 and 3
+        propose_number(&deployer.voting_handle, 9).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_retracting_more_votes_than_allocated() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+
+        propose_numbers(&deployer.voting_handle, &[1, 2]).await;
+        vote_for_number(&user_1.voting_handle, 1, 200_000).await;
+        vote_for_number(&user_1.voting_handle, 2, 200_000).await;
+
+        // the votes for 2 cannot be retracted from 1
+        retract_vote(&user_1.voting_handle, 1, 300_000).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_moving_another_users_votes() {
+        let (token_contract_id, token_handle, [deployer, user_1, user_2, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+
+        propose_numbers(&deployer.voting_handle, &[1, 2]).await;
+        vote_for_number(&user_1.voting_handle, 1, 200_000).await;
+
+        move_vote(&user_2.voting_handle, 1, 2, 100_000).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_quadratic_votes_cost_more_than_the_balance() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract in quadratic mode
+        initialize_voting_contract_with_config(
+            token_contract_id,
+            &deployer,
+            VotingConfig {
+                voting_mode: VotingMode::Quadratic(),
+                ..default_config()
+            },
+        )
+        .await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 1_000_000).await;
+
+        propose_numbers(&deployer.voting_handle, &[1]).await;
+
+        // 1_000 votes cost the whole deposit, one more does not fit
+        vote_for_number(&user_1.voting_handle, 1, 1_000).await;
+        vote_for_number(&user_1.voting_handle, 1, 1).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_proposing_a_number_twice() {
+        let (token_contract_id, _, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        propose_number(&deployer.voting_handle, 5).await;
+        propose_number(&user_1.voting_handle, 5).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_initializing_without_a_proposal_bond() {
+        let (token_contract_id, _, [deployer, _, _, _]) = setup_tests().await;
+
+        initialize_voting_contract_with_config(
+            token_contract_id,
+            &deployer,
+            VotingConfig {
+                proposal_bond: 0,
+                ..default_config()
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_proposing_too_many_numbers() {
+        let (token_contract_id, _, [deployer, _, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        // a single proposer can make 10 proposals per round
+        let numbers: Vec<u64> = (0..11).collect();
+        propose_numbers(&deployer.voting_handle, &numbers).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_proposing_without_the_bond() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract with a proposal bond
+        initialize_voting_contract_with_config(
+            token_contract_id,
+            &deployer,
+            VotingConfig {
+                proposal_bond: 10_000,
+                ..default_config()
+            },
+        )
+        .await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 9_999).await;
+        propose_number(&user_1.voting_handle, 5).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_reading_a_round_that_is_not_over() {
+        let (token_contract_id, _, [deployer, _, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        get_round(&deployer.voting_handle, 0).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_unlocking_votes_before_the_round_is_over() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+        vote_for_number(&user_1.voting_handle, 5, 500_000).await;
+
+        unlock_votes(&user_1.voting_handle, 0).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_unlocking_votes_twice() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+        vote_for_number(&user_1.voting_handle, 5, 500_000).await;
+        execute_in_voting_contract(&deployer).await;
+
+        unlock_votes(&user_1.voting_handle, 0).await;
+        unlock_votes(&user_1.voting_handle, 0).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_losing_voter_claims_reward() {
+        let (token_contract_id, token_handle, [deployer, user_1, user_2, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, deployer.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_2.wallet.address()).await;
+
+        fund_rewards(&deployer.voting_handle, token_contract_id, 1_000_000).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        deposit_into_voting_contract(&user_2.voting_handle, token_contract_id, 500_000).await;
+        propose_numbers(&deployer.voting_handle, &[5, 6]).await;
+        vote_for_number(&user_1.voting_handle, 5, 200_000).await;
+        vote_for_number(&user_2.voting_handle, 6, 100_000).await;
+        execute_in_voting_contract(&deployer).await;
+
+        claim_reward(&user_2.voting_handle, 0).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_claiming_reward_twice() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, deployer.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+
+        fund_rewards(&deployer.voting_handle, token_contract_id, 1_000_000).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+        vote_for_number(&user_1.voting_handle, 5, 200_000).await;
+        execute_in_voting_contract(&deployer).await;
+
+        claim_reward(&user_1.voting_handle, 0).await;
+        claim_reward(&user_1.voting_handle, 0).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_claiming_reward_after_the_claim_period() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, deployer.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+
+        fund_rewards(&deployer.voting_handle, token_contract_id, 1_000_000).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+        vote_for_number(&user_1.voting_handle, 5, 200_000).await;
+        execute_in_voting_contract(&deployer).await;
+
+        close_claim_period(&deployer, 0).await;
+        claim_reward(&user_1.voting_handle, 0).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_sweeping_rewards_during_the_claim_period() {
+        let (token_contract_id, token_handle, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        mint_and_send_to_address(&token_handle, 1_000_000, deployer.wallet.address()).await;
+        mint_and_send_to_address(&token_handle, 1_000_000, user_1.wallet.address()).await;
+
+        fund_rewards(&deployer.voting_handle, token_contract_id, 1_000_000).await;
+        deposit_into_voting_contract(&user_1.voting_handle, token_contract_id, 500_000).await;
+        propose_numbers(&deployer.voting_handle, &[5]).await;
+        vote_for_number(&user_1.voting_handle, 5, 200_000).await;
+        execute_in_voting_contract(&deployer).await;
+
+        sweep_rewards(&deployer.voting_handle, 0).await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_non_owner_transfers_ownership() {
+        let (token_contract_id, _, [deployer, user_1, _, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        transfer_ownership(
+            &user_1.voting_handle,
+            Identity::Address(user_1.wallet.address()),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Revert(42)")]
+    async fn when_non_pending_owner_accepts_ownership() {
+        let (token_contract_id, _, [deployer, user_1, user_2, _]) = setup_tests().await;
+
+        // initialize the voting contract
+        initialize_voting_contract(token_contract_id, &deployer).await;
+
+        transfer_ownership(
+            &deployer.voting_handle,
+            Identity::Address(user_1.wallet.address()),
+        )
+        .await;
+        accept_ownership(&user_2.voting_handle).await;
+    }
 }
@@ -1,10 +1,28 @@
 #![allow(dead_code)]
 
-use fuels::{prelude::*, tx::ContractId};
+use fuels::{
+    prelude::*,
+    signers::fuel_crypto::Hasher,
+    tx::{Bytes32, ContractId, Receipt, StorageSlot},
+};
+use std::collections::HashMap;
 
 // Load abi from json
 abigen!(Voting, "./out/debug/voting-abi.json");
 abigen!(MyToken, "../solution_token/out/debug/token-abi.json");
+abigen!(
+    GovernanceTarget,
+    "./tests/artifacts/governance_target/out/debug/governance_target-abi.json"
+);
+
+// the bond locked by every proposal in the default config
+pub(crate) const PROPOSAL_BOND: u64 = 10;
+
+// the tokens the deployer deposits when initializing, enough to pay the bonds of the proposals it makes
+pub(crate) const DEPLOYER_DEPOSIT: u64 = 1_000;
+
+// the number of blocks each round opened by the helpers accepts votes for, longer than any test round
+pub(crate) const ROUND_LENGTH: u64 = 100;
 
 pub(crate) struct User {
     pub(crate) voting_handle: Voting,
@@ -12,40 +30,78 @@ pub(crate) struct User {
 }
 
 pub(crate) async fn setup_tests() -> (ContractId, MyToken, [User; 4]) {
-    let num_wallets = 4;
     let coins_per_wallet = 1;
     let amount_per_coin = 1_000_000;
-    let config = WalletsConfig::new(
-        Some(num_wallets),
-        Some(coins_per_wallet),
-        Some(amount_per_coin),
-    );
-    let mut wallets = launch_custom_provider_and_get_wallets(config, None).await;
-    let deployer_wallet = wallets.pop().unwrap();
-    let user_1_wallet = wallets.pop().unwrap();
-    let user_2_wallet = wallets.pop().unwrap();
-    let user_3_wallet = wallets.pop().unwrap();
 
+    let mut deployer_wallet = LocalWallet::new_random(None);
+    let mut user_1_wallet = LocalWallet::new_random(None);
+    let mut user_2_wallet = LocalWallet::new_random(None);
+    let mut user_3_wallet = LocalWallet::new_random(None);
+
+    let mut coins = vec![];
+    for wallet in [
+        &deployer_wallet,
+        &user_1_wallet,
+        &user_2_wallet,
+        &user_3_wallet,
+    ] {
+        coins.extend(setup_single_asset_coins(
+            wallet.address(),
+            BASE_ASSET_ID,
+            coins_per_wallet,
+            amount_per_coin,
+        ));
+    }
+
+    let (provider, _) = setup_test_provider(coins, None).await;
+    deployer_wallet.set_provider(provider.clone());
+    user_1_wallet.set_provider(provider.clone());
+    user_2_wallet.set_provider(provider.clone());
+    user_3_wallet.set_provider(provider);
+
+    // the deployer is the admin that the token expects to initialize it
     let token_contract_id = Contract::deploy(
         "../solution_token/out/debug/token.bin",
         &deployer_wallet,
         TxParameters::default(),
-        StorageConfiguration::with_storage_path(Some(
-            "../solution_token/out/debug/token-storage_slots.json".to_string(),
-        )),
+        storage_with_admin(
+            "../solution_token/out/debug/token-storage_slots.json",
+            deployer_wallet.address(),
+        ),
     )
     .await
     .unwrap();
 
     let token_handle = MyToken::new(token_contract_id.to_string(), deployer_wallet.clone());
 
+    // the deployer administers the token and is its only minter
+    token_handle
+        .initialize(
+            Identity::Address(deployer_wallet.address()),
+            "Favorite Number Governance Token".to_string(),
+            "FAVNUM  ".to_string(),
+            9,
+            1_000_000_000,
+        )
+        .call()
+        .await
+        .unwrap();
+    token_handle
+        .grant_minter(Identity::Address(deployer_wallet.address()), 1_000_000_000)
+        .call()
+        .await
+        .unwrap();
+    mint_and_send_to_address(&token_handle, DEPLOYER_DEPOSIT, deployer_wallet.address()).await;
+
+    // the deployer is the admin that the voting contract expects to initialize it
     let voting_contract_id = Contract::deploy(
         "./out/debug/voting.bin",
         &deployer_wallet,
         TxParameters::default(),
-        StorageConfiguration::with_storage_path(Some(
-            "./out/debug/voting-storage_slots.json".to_string(),
-        )),
+        storage_with_admin(
+            "./out/debug/voting-storage_slots.json",
+            deployer_wallet.address(),
+        ),
     )
     .await
     .unwrap();
@@ -74,15 +130,55 @@ pub(crate) async fn setup_tests() -> (ContractId, MyToken, [User; 4]) {
     )
 }
 
-pub(crate) async fn initialize_voting_contract(
+// the storage slots at `path` with `admin` written into the slot of the `admin` field, which is the first
+// field of the storage block of both the token and the voting contract
+fn storage_with_admin(path: &str, admin: Address) -> StorageConfiguration {
+    let admin_key = Hasher::hash(b"storage_0");
+
+    // replace the default slot rather than adding a second one with the same key
+    let slots = std::fs::read_to_string(path).unwrap();
+    let mut slots: Vec<StorageSlot> = serde_json::from_str(&slots).unwrap();
+    slots.retain(|slot| *slot.key() != admin_key);
+    slots.push(StorageSlot::new(admin_key, Bytes32::from(*admin)));
+    // transactions need their storage slots in order
+    slots.sort_by_key(|slot| *slot.key());
+
+    StorageConfiguration::new(None, Some(slots))
+}
+
+pub(crate) fn default_config() -> VotingConfig {
+    VotingConfig {
+        proposal_bond: PROPOSAL_BOND,
+        quorum: 0,
+        tie_policy: TiePolicy::KeepIncumbent(),
+        voting_mode: VotingMode::TokenWeighted(),
+    }
+}
+
+pub(crate) async fn initialize_voting_contract(token_contract_id: ContractId, initializer: &User) {
+    initialize_voting_contract_with_config(token_contract_id, initializer, default_config()).await;
+}
+
+// initializes the contract, deposits DEPLOYER_DEPOSIT from the initializer so that it can make proposals
+// and opens round 0
+pub(crate) async fn initialize_voting_contract_with_config(
     token_contract_id: ContractId,
-    voting_handle: &Voting,
+    initializer: &User,
+    config: VotingConfig,
 ) {
-    voting_handle
-        .initialize(token_contract_id)
+    initializer
+        .voting_handle
+        .initialize(token_contract_id, config)
         .call()
         .await
         .unwrap();
+    deposit_into_voting_contract(
+        &initializer.voting_handle,
+        token_contract_id,
+        DEPLOYER_DEPOSIT,
+    )
+    .await;
+    open_round(initializer).await;
 }
 
 pub(crate) async fn mint_and_send_to_address(
@@ -102,7 +198,7 @@ pub(crate) async fn deposit_into_voting_contract(
     voting_handle: &Voting,
     token_contract_id: ContractId,
     asset_amount: u64,
-) {
+) -> Vec<Receipt> {
     let tx_params = TxParameters::new(
         None,             // gas price
         Some(10_000_000), // gas limit
@@ -120,50 +216,376 @@ pub(crate) async fn deposit_into_voting_contract(
         .call_params(call_params)
         .call()
         .await
-        .unwrap();
+        .unwrap()
+        .receipts
 }
 
-pub(crate) async fn withdraw_from_voting_contract(voting_handle: &Voting, asset_amount: u64) {
+pub(crate) async fn withdraw_from_voting_contract(
+    voting_handle: &Voting,
+    asset_amount: u64,
+) -> Vec<Receipt> {
     voting_handle
         .withdraw(asset_amount)
         .append_variable_outputs(1)
         .call()
         .await
+        .unwrap()
+        .receipts
+}
+
+pub(crate) async fn propose_number(voting_handle: &Voting, number: u64) -> Vec<Receipt> {
+    voting_handle.propose(number).call().await.unwrap().receipts
+}
+
+pub(crate) async fn propose_numbers(voting_handle: &Voting, numbers: &[u64]) {
+    for number in numbers {
+        propose_number(voting_handle, *number).await;
+    }
+}
+
+pub(crate) async fn propose_call(voting_handle: &Voting, number: u64, call: ContractCall) {
+    voting_handle
+        .propose_call(number, call)
+        .call()
+        .await
         .unwrap();
 }
 
-pub(crate) async fn vote_for_number(voting_handle: &Voting, voting_for: u64, vote_amount: u64) {
+// deploy the contract that records the calls that winning proposals make, it has to be built
+// separately with `forc build` in tests/artifacts/governance_target
+pub(crate) async fn deploy_governance_target(
+    wallet: &LocalWallet,
+) -> (ContractId, GovernanceTarget) {
+    let target_id = Contract::deploy(
+        "./tests/artifacts/governance_target/out/debug/governance_target.bin",
+        wallet,
+        TxParameters::default(),
+        StorageConfiguration::with_storage_path(Some(
+            "./tests/artifacts/governance_target/out/debug/governance_target-storage_slots.json"
+                .to_string(),
+        )),
+    )
+    .await
+    .unwrap();
+
+    let target_handle = GovernanceTarget::new(target_id.to_string(), wallet.clone());
+
+    (target_id, target_handle)
+}
+
+pub(crate) async fn get_proposal_count(voting_handle: &Voting) -> u64 {
+    voting_handle
+        .get_proposal_count()
+        .call()
+        .await
+        .unwrap()
+        .value
+}
+
+pub(crate) async fn vote_for_number(
+    voting_handle: &Voting,
+    voting_for: u64,
+    vote_amount: u64,
+) -> Vec<Receipt> {
     voting_handle
         .vote(voting_for, vote_amount)
         .call()
         .await
-        .unwrap();
+        .unwrap()
+        .receipts
+}
+
+pub(crate) async fn retract_vote(voting_handle: &Voting, number: u64, amount: u64) -> Vec<Receipt> {
+    voting_handle
+        .retract_vote(number, amount)
+        .call()
+        .await
+        .unwrap()
+        .receipts
 }
 
-pub(crate) async fn execute_in_voting_contract(voting_handle: &Voting) -> bool {
+pub(crate) async fn move_vote(
+    voting_handle: &Voting,
+    from: u64,
+    to: u64,
+    amount: u64,
+) -> Vec<Receipt> {
     voting_handle
+        .move_vote(from, to, amount)
+        .call()
+        .await
+        .unwrap()
+        .receipts
+}
+
+// wait for the voting window of the current round to close, execute it and open the next round, or the
+// same one again if execute found too few votes. a runoff opens its own window
+pub(crate) async fn execute_in_voting_contract(owner: &User) -> ExecuteOutcome {
+    close_window(owner).await;
+    let outcome = owner
+        .voting_handle
         .execute()
         .append_variable_outputs(1)
         .call()
         .await
         .unwrap()
+        .value;
+
+    if outcome != ExecuteOutcome::RunoffStarted() {
+        open_round(owner).await;
+    }
+    outcome
+}
+
+// make the pending call of a round won by a call proposal to `target`, which has to be an input of the
+// transaction
+pub(crate) async fn carry_out(
+    voting_handle: &Voting,
+    round: u64,
+    target: ContractId,
+) -> ExecuteOutcome {
+    voting_handle
+        .carry_out(round)
+        .set_contracts(&[target])
+        .call()
+        .await
+        .unwrap()
+        .value
+}
+
+// schedule the current round to accept votes for the next ROUND_LENGTH blocks
+pub(crate) async fn open_round(owner: &User) {
+    let now = get_block_height(&owner.wallet).await;
+    schedule_round(&owner.voting_handle, now, now + ROUND_LENGTH).await;
+}
+
+// advance until the voting window of the current round has closed
+pub(crate) async fn close_window(user: &User) {
+    let round = get_round_count(&user.voting_handle).await;
+    let window = get_round_window(&user.voting_handle, round).await;
+    advance_to_height(&user.wallet, window.end).await;
+}
+
+pub(crate) async fn initialize_with_tie_policy(
+    token_contract_id: ContractId,
+    initializer: &User,
+    tie_policy: TiePolicy,
+) {
+    initialize_voting_contract_with_config(
+        token_contract_id,
+        initializer,
+        VotingConfig {
+            tie_policy,
+            ..default_config()
+        },
+    )
+    .await;
+}
+
+// a round where 5 and 3 are tied for the most votes and 8 is behind, 5 reached its tally before 3
+pub(crate) async fn setup_tied_round(tie_policy: TiePolicy) -> (ContractId, [User; 4]) {
+    let (token_contract_id, token_handle, [deployer, user_1, user_2, user_3]) = setup_tests().await;
+
+    initialize_with_tie_policy(token_contract_id, &deployer, tie_policy).await;
+
+    for user in [&user_1, &user_2, &user_3] {
+        mint_and_send_to_address(&token_handle, 1_000_000, user.wallet.address()).await;
+        deposit_into_voting_contract(&user.voting_handle, token_contract_id, 200_000).await;
+    }
+
+    propose_numbers(&deployer.voting_handle, &[3, 5, 8]).await;
+
+    vote_for_number(&user_1.voting_handle, 5, 200_000).await;
+    vote_for_number(&user_2.voting_handle, 3, 200_000).await;
+    vote_for_number(&user_3.voting_handle, 8, 100_000).await;
+
+    (token_contract_id, [deployer, user_1, user_2, user_3])
+}
+
+pub(crate) async fn unlock_votes(voting_handle: &Voting, round: u64) -> u64 {
+    voting_handle
+        .unlock_votes(round)
+        .call()
+        .await
+        .unwrap()
+        .value
+}
+
+pub(crate) async fn fund_rewards(
+    voting_handle: &Voting,
+    token_contract_id: ContractId,
+    asset_amount: u64,
+) -> Vec<Receipt> {
+    let call_params = CallParameters::new(
+        Some(asset_amount),                      // amount
+        Some(AssetId::from(*token_contract_id)), // asset ID
+        Some(1_000_000),                         // gas forwarded
+    );
+    voting_handle
+        .fund_rewards()
+        .call_params(call_params)
+        .call()
+        .await
+        .unwrap()
+        .receipts
+}
+
+pub(crate) async fn claim_reward(voting_handle: &Voting, round: u64) -> u64 {
+    voting_handle
+        .claim_reward(round)
+        .call()
+        .await
+        .unwrap()
+        .value
+}
+
+pub(crate) async fn sweep_rewards(voting_handle: &Voting, round: u64) -> u64 {
+    voting_handle
+        .sweep_rewards(round)
+        .call()
+        .await
+        .unwrap()
+        .value
+}
+
+// the number of blocks after a round is executed during which its rewards can be claimed
+pub(crate) const REWARD_CLAIM_PERIOD: u64 = 1_000;
+
+// advance past the claim period of a round that has been executed
+pub(crate) async fn close_claim_period(user: &User, round: u64) {
+    let executed_at = get_round(&user.voting_handle, round).await.height;
+    advance_to_height(&user.wallet, executed_at + REWARD_CLAIM_PERIOD).await;
+}
+
+pub(crate) async fn get_reward_pool(voting_handle: &Voting, round: u64) -> u64 {
+    voting_handle
+        .get_reward_pool(round)
+        .call()
+        .await
+        .unwrap()
+        .value
+}
+
+// reference calculation of a voter's share of a reward pool, rounded down like the contract does
+pub(crate) fn expected_reward(pool: u64, user_votes: u64, winning_votes: u64) -> u64 {
+    (pool as u128 * user_votes as u128 / winning_votes as u128) as u64
+}
+
+pub(crate) async fn get_round_count(voting_handle: &Voting) -> u64 {
+    voting_handle.get_round_count().call().await.unwrap().value
+}
+
+pub(crate) async fn get_round(voting_handle: &Voting, round: u64) -> RoundResult {
+    voting_handle.get_round(round).call().await.unwrap().value
+}
+
+// reference calculation of what holding `votes` votes on a number costs in quadratic mode
+pub(crate) fn quadratic_cost(votes: u64) -> u64 {
+    votes
+        .checked_mul(votes)
+        .expect("cost does not fit in a u64")
+}
+
+// reference model of a quadratic round, tracking the votes by user index and number
+#[derive(Default)]
+pub(crate) struct QuadraticRound {
+    user_votes: HashMap<(usize, u64), u64>,
+    pub(crate) tallies: HashMap<u64, u64>,
+    pub(crate) spent: HashMap<usize, u64>,
+}
+
+impl QuadraticRound {
+    pub(crate) fn vote(&mut self, user: usize, number: u64, votes: u64) {
+        let held = self.user_votes.entry((user, number)).or_default();
+        let cost = quadratic_cost(*held + votes) - quadratic_cost(*held);
+        *held += votes;
+        *self.tallies.entry(number).or_default() += votes;
+        *self.spent.entry(user).or_default() += cost;
+    }
+
+    pub(crate) fn retract(&mut self, user: usize, number: u64, votes: u64) {
+        let held = self.user_votes.get_mut(&(user, number)).unwrap();
+        let refund = quadratic_cost(*held) - quadratic_cost(*held - votes);
+        *held -= votes;
+        *self.tallies.get_mut(&number).unwrap() -= votes;
+        *self.spent.get_mut(&user).unwrap() -= refund;
+    }
+}
+
+pub(crate) async fn schedule_round(voting_handle: &Voting, start: u64, end: u64) {
+    voting_handle
+        .schedule_round(start, end)
+        .call()
+        .await
+        .unwrap();
+}
+
+pub(crate) async fn get_round_window(voting_handle: &Voting, round: u64) -> RoundWindow {
+    voting_handle
+        .get_round_window(round)
+        .call()
+        .await
+        .unwrap()
         .value
 }
 
+pub(crate) async fn get_block_height(wallet: &LocalWallet) -> u64 {
+    wallet
+        .get_provider()
+        .unwrap()
+        .chain_info()
+        .await
+        .unwrap()
+        .latest_block
+        .height
+        .0
+}
+
+pub(crate) async fn advance_blocks(wallet: &LocalWallet, amount: u64) {
+    wallet
+        .get_provider()
+        .unwrap()
+        .produce_blocks(amount)
+        .await
+        .unwrap();
+}
+
+// advance until the next transaction is included in block `height` or later
+pub(crate) async fn advance_to_height(wallet: &LocalWallet, height: u64) {
+    let current = get_block_height(wallet).await;
+    if current + 1 < height {
+        advance_blocks(wallet, height - current - 1).await;
+    }
+}
+
 pub(crate) async fn get_contract_balance(voting_handle: &Voting) -> u64 {
     voting_handle.get_balance().call().await.unwrap().value
 }
 
+// read the balance of the user the handle belongs to
 pub(crate) async fn get_user_balance(voting_handle: &Voting) -> u64 {
+    voting_handle.get_user_balance().call().await.unwrap().value
+}
+
+// read the balance of any user, the handle does not have to belong to them
+pub(crate) async fn get_user_balance_of(voting_handle: &Voting, user: Identity) -> u64 {
     voting_handle
-        .get_user_balance()
-        .append_variable_outputs(1)
+        .get_user_balance_of(user)
         .call()
         .await
         .unwrap()
         .value
 }
 
+// read a user's balance through get_user_balance_of
+pub(crate) async fn get_balance_of(user: &User) -> u64 {
+    get_user_balance_of(
+        &user.voting_handle,
+        Identity::Address(user.wallet.address()),
+    )
+    .await
+}
+
 pub(crate) async fn get_token_balance_in_wallet(
     token_contract_id: ContractId,
     wallet: LocalWallet,
@@ -197,3 +619,37 @@ pub(crate) async fn get_number_of_votes(voting_handle: &Voting, number: u64) ->
         .unwrap()
         .value
 }
+
+pub(crate) async fn get_user_votes(voting_handle: &Voting, user: Identity, number: u64) -> u64 {
+    voting_handle
+        .get_user_votes(user, number)
+        .call()
+        .await
+        .unwrap()
+        .value
+}
+
+pub(crate) async fn get_locked_balance(voting_handle: &Voting, user: Identity) -> u64 {
+    voting_handle
+        .get_locked_balance(user)
+        .call()
+        .await
+        .unwrap()
+        .value
+}
+
+pub(crate) async fn transfer_ownership(voting_handle: &Voting, new_owner: Identity) {
+    voting_handle
+        .transfer_ownership(new_owner)
+        .call()
+        .await
+        .unwrap();
+}
+
+pub(crate) async fn accept_ownership(voting_handle: &Voting) {
+    voting_handle.accept_ownership().call().await.unwrap();
+}
+
+pub(crate) async fn get_owner(voting_handle: &Voting) -> Identity {
+    voting_handle.owner().call().await.unwrap().value
+}